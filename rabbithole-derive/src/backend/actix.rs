@@ -1,29 +1,46 @@
 use quote::quote;
 
-pub fn generate_app(service: &syn::Path, ty: &str) -> proc_macro2::TokenStream {
+/// The scope of the routes of `service`, where `PATCH /<ty>/<id>` is routed only for the
+/// `patchable` entities
+pub fn generate_app(service: &syn::Path, ty: &str, patchable: bool) -> proc_macro2::TokenStream {
+    let (update_route, single_methods) = if patchable {
+        (
+            quote!(.route(web::patch().to(rabbithole_endpoint_actix::ActixSettings::update_resource::<Self>))),
+            quote!(&["GET", "PATCH", "DELETE"]),
+        )
+    } else {
+        (quote!(), quote!(&["GET", "DELETE"]))
+    };
     quote! {
         impl #service {
             pub fn actix_service() -> actix_web::Scope {
                 use actix_web::{web, guard};
                 web::scope(#ty)
+                    .app_data(rabbithole_endpoint_actix::ActixSettings::json_config())
+                    .app_data(rabbithole_endpoint_actix::ActixSettings::path_config())
                     .service(web::resource("")
                             .route(web::get().to(rabbithole_endpoint_actix::ActixSettings::fetch_collection::<Self>))
                             .route(web::post().to(rabbithole_endpoint_actix::ActixSettings::create::<Self>))
+                            .default_service(web::to(|req: actix_web::HttpRequest| rabbithole_endpoint_actix::ActixSettings::method_not_allowed(req, &["GET", "POST"])))
                         )
                     .service(web::resource("/{id}")
                             .route(web::get().to( rabbithole_endpoint_actix::ActixSettings::fetch_single::<Self>))
-                            .route(web::patch().to(rabbithole_endpoint_actix::ActixSettings::update_resource::<Self>))
+                            #update_route
                             .route(web::delete().to(rabbithole_endpoint_actix::ActixSettings::delete_resource::<Self>))
+                            .default_service(web::to(|req: actix_web::HttpRequest| rabbithole_endpoint_actix::ActixSettings::method_not_allowed(req, #single_methods)))
                             )
                     .service(web::resource("/{id}/relationships/{related_fields}")
                             .route(web::get().to( rabbithole_endpoint_actix::ActixSettings::fetch_relationship::<Self>))
                             .route(web::patch().to(rabbithole_endpoint_actix::ActixSettings::replace_relationship::<Self>))
                             .route(web::post().to(rabbithole_endpoint_actix::ActixSettings::add_relationship::<Self>))
                             .route(web::delete().to(rabbithole_endpoint_actix::ActixSettings::remove_relationship::<Self>))
+                            .default_service(web::to(|req: actix_web::HttpRequest| rabbithole_endpoint_actix::ActixSettings::method_not_allowed(req, &["GET", "PATCH", "POST", "DELETE"])))
                         )
                     .service(web::resource("/{id}/{related_fields}")
                             .route(web::get().to( rabbithole_endpoint_actix::ActixSettings::fetch_related::<Self>))
+                            .default_service(web::to(|req: actix_web::HttpRequest| rabbithole_endpoint_actix::ActixSettings::method_not_allowed(req, &["GET"])))
                         )
+                    .default_service(web::to(rabbithole_endpoint_actix::ActixSettings::route_not_found))
             }
         }
     }
@@ -0,0 +1,225 @@
+use crate::error::EntityDecoratorError;
+use quote::quote;
+
+/// The wrappers of the entities: a newtype, or an enum each variant of which wraps one
+pub enum Delegate<'a> {
+    Newtype(&'a syn::Type),
+    Variants(Vec<(&'a syn::Ident, &'a syn::Type)>),
+}
+
+/// The only field of the unnamed `fields`, which should wrap an entity
+fn wrapped<'a>(
+    fields: &'a syn::Fields, tokens: &impl quote::ToTokens,
+) -> syn::Result<&'a syn::Type> {
+    match fields {
+        syn::Fields::Unnamed(syn::FieldsUnnamed { unnamed, .. }) if unnamed.len() == 1 => {
+            Ok(&unnamed[0].ty)
+        },
+        _ => Err(syn::Error::new_spanned(
+            tokens,
+            EntityDecoratorError::InvalidVariant,
+        )),
+    }
+}
+
+/// Returns the `Delegate` of `ast` if it wraps the other entities, or `None` for the plain
+/// entities, the named structs
+pub fn get_delegate(ast: &syn::DeriveInput) -> syn::Result<Option<Delegate<'_>>> {
+    match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: fields @ syn::Fields::Unnamed(_),
+            ..
+        }) => Ok(Some(Delegate::Newtype(wrapped(fields, &ast.ident)?))),
+        syn::Data::Enum(syn::DataEnum { variants, .. }) if !variants.is_empty() => {
+            let mut res = vec![];
+            for variant in variants {
+                res.push((&variant.ident, wrapped(&variant.fields, variant)?));
+            }
+            Ok(Some(Delegate::Variants(res)))
+        },
+        syn::Data::Enum(_) => Err(syn::Error::new_spanned(
+            &ast.ident,
+            EntityDecoratorError::InvalidVariant,
+        )),
+        _ => Ok(None),
+    }
+}
+
+/// Implements the traits of the entities for `ast` by handing the calls to the wrapped entities
+///
+/// `entity_type` is the type of the enum itself, used by its routes, while each of the rendered
+/// resources keeps the type of its own variant
+pub fn generate_delegate(
+    ast: &syn::DeriveInput, delegate: &Delegate, entity_type: Option<&str>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let decorated = &ast.ident;
+    let (ctors, inners): (Vec<proc_macro2::TokenStream>, Vec<&syn::Type>) = match delegate {
+        Delegate::Newtype(inner) => (vec![quote!(Self)], vec![inner]),
+        Delegate::Variants(variants) => variants
+            .iter()
+            .map(|(ident, inner)| (quote!(Self::#ident), *inner))
+            .unzip(),
+    };
+    let ctors = &ctors;
+
+    let (single, from_resource, validate) = match delegate {
+        Delegate::Newtype(inner) => (
+            quote! {
+                type Id = <#inner as rabbithole::entity::SingleEntity>::Id;
+
+                fn ty() -> std::string::String { <#inner as rabbithole::entity::SingleEntity>::ty() }
+                fn parse_id(id: &str) -> rabbithole::Result<Self::Id> { <#inner as rabbithole::entity::SingleEntity>::parse_id(id) }
+                fn accepts_type(ty: &str) -> bool { <#inner as rabbithole::entity::SingleEntity>::accepts_type(ty) }
+                fn is_writable(field: &str) -> bool { <#inner as rabbithole::entity::SingleEntity>::is_writable(field) }
+            },
+            quote! {
+                Ok(Self(<#inner as rabbithole::entity::FromResource<L>>::from_resource(resource, lookup).await?))
+            },
+            quote! {
+                <#inner as rabbithole::validation::Validate>::validate(resource, partial)
+            },
+        ),
+        Delegate::Variants(_) => {
+            let entity_type = entity_type.ok_or_else(|| {
+                syn::Error::new_spanned(decorated, EntityDecoratorError::InvalidEntityType)
+            })?;
+            let expected = quote! {
+                rabbithole::entity::unknown_variant(resource, &[ #( <#inners as rabbithole::entity::SingleEntity>::ty(), )* ])
+            };
+            (
+                quote! {
+                    type Id = std::string::String;
+
+                    fn ty() -> std::string::String { #entity_type.to_string() }
+                    fn parse_id(id: &str) -> rabbithole::Result<Self::Id> { rabbithole::entity::id_from_path(id) }
+                    fn accepts_type(ty: &str) -> bool { #( <#inners as rabbithole::entity::SingleEntity>::accepts_type(ty) )||* }
+                    fn is_writable(field: &str) -> bool { #( <#inners as rabbithole::entity::SingleEntity>::is_writable(field) )&&* }
+                },
+                quote! {
+                    #(
+                        if resource.id.ty == <#inners as rabbithole::entity::SingleEntity>::ty() {
+                            return Ok(#ctors(<#inners as rabbithole::entity::FromResource<L>>::from_resource(resource, lookup).await?));
+                        }
+                    )*
+                    Err(#expected)
+                },
+                quote! {
+                    #(
+                        if resource.id.ty == <#inners as rabbithole::entity::SingleEntity>::ty() {
+                            return <#inners as rabbithole::validation::Validate>::validate(resource, partial);
+                        }
+                    )*
+                    Err(rabbithole::model::error::Errors(vec![#expected]))
+                },
+            )
+        },
+    };
+
+    let patch = match delegate {
+        Delegate::Newtype(inner) => {
+            let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+            quote! {
+                impl #impl_generics rabbithole::entity::Patchable for #decorated #ty_generics #where_clause {
+                    type Patch = <#inner as rabbithole::entity::Patchable>::Patch;
+
+                    fn apply_patch(&mut self, patch: Self::Patch) { rabbithole::entity::Patchable::apply_patch(&mut self.0, patch) }
+                }
+            }
+        },
+        // A patch could turn a variant into another one, so the enums cannot be patched
+        Delegate::Variants(_) => quote!(),
+    };
+
+    let mut generics = ast.generics.clone();
+    generics.params.push(syn::parse_quote!(L));
+    let predicates = &mut generics.make_where_clause().predicates;
+    predicates.push(syn::parse_quote!(L: std::marker::Sync));
+    for inner in &inners {
+        predicates.push(syn::parse_quote!(#inner: rabbithole::entity::FromResource<L>));
+    }
+    let (lookup_impl_generics, _, lookup_where_clause) = generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics rabbithole::entity::Entity for #decorated #ty_generics #where_clause {
+            fn included(&self, uri: &str,
+                include_query: &std::option::Option<rabbithole::query::IncludeQuery>,
+                fields_query: &rabbithole::query::FieldsQuery,
+                visibility: &dyn rabbithole::policy::FieldVisibility,
+            ) -> rabbithole::Result<rabbithole::model::document::Included> {
+                match self { #( #ctors(e) => rabbithole::entity::Entity::included(e, uri, include_query, fields_query, visibility), )* }
+            }
+
+            fn to_document(&self, uri: &str, query: &rabbithole::query::Query, request_path: http::Uri, additional_links: rabbithole::model::link::Links, additional_meta: rabbithole::model::Meta,) -> rabbithole::Result<rabbithole::model::document::Document> {
+                rabbithole::entity::SingleEntity::to_document(self, uri, query, request_path, additional_links, additional_meta)
+            }
+        }
+
+        impl #impl_generics rabbithole::entity::SingleEntity for #decorated #ty_generics #where_clause {
+            #single
+
+            fn id(&self) -> std::string::String {
+                match self { #( #ctors(e) => rabbithole::entity::SingleEntity::id(e), )* }
+            }
+
+            fn attributes(&self) -> rabbithole::model::resource::Attributes {
+                match self { #( #ctors(e) => rabbithole::entity::SingleEntity::attributes(e), )* }
+            }
+
+            fn relationships(&self, uri: &str) -> rabbithole::model::relationship::Relationships {
+                match self { #( #ctors(e) => rabbithole::entity::SingleEntity::relationships(e, uri), )* }
+            }
+
+            fn links(&self, uri: &str) -> rabbithole::model::link::Links {
+                match self { #( #ctors(e) => rabbithole::entity::SingleEntity::links(e, uri), )* }
+            }
+
+            fn meta(&self) -> rabbithole::model::Meta {
+                match self { #( #ctors(e) => rabbithole::entity::SingleEntity::meta(e), )* }
+            }
+
+            fn linkage(&self, field: &str) -> rabbithole::model::relationship::Linkage {
+                match self { #( #ctors(e) => rabbithole::entity::SingleEntity::linkage(e, field), )* }
+            }
+
+            fn related_document(&self, field: &str, uri: &str, query: &rabbithole::query::Query, request_path: http::Uri) -> rabbithole::Result<rabbithole::model::document::Document> {
+                match self { #( #ctors(e) => rabbithole::entity::SingleEntity::related_document(e, field, uri, query, request_path), )* }
+            }
+
+            fn to_resource_identifier(&self) -> std::option::Option<rabbithole::model::resource::ResourceIdentifier> {
+                match self { #( #ctors(e) => rabbithole::entity::SingleEntity::to_resource_identifier(e), )* }
+            }
+
+            fn to_resource(&self, uri: &str, fields_query: &rabbithole::query::FieldsQuery, visibility: &dyn rabbithole::policy::FieldVisibility) -> std::option::Option<rabbithole::model::resource::Resource> {
+                match self { #( #ctors(e) => rabbithole::entity::SingleEntity::to_resource(e, uri, fields_query, visibility), )* }
+            }
+
+            fn to_relationship_links(&self, field_name: &str, uri: &str) -> rabbithole::model::relationship::RelationshipLinks {
+                match self { #( #ctors(e) => rabbithole::entity::SingleEntity::to_relationship_links(e, field_name, uri), )* }
+            }
+
+            fn cmp_field(&self, field: &str, other: &Self) -> rabbithole::Result<std::cmp::Ordering> {
+                #[allow(unreachable_patterns)]
+                match (self, other) {
+                    #( (#ctors(e), #ctors(other)) => rabbithole::entity::SingleEntity::cmp_field(e, field, other), )*
+                    _ => rabbithole::entity::SingleEntity::attributes(self).cmp(field, &rabbithole::entity::SingleEntity::attributes(other)),
+                }
+            }
+        }
+
+        #[rabbithole::async_trait]
+        impl #lookup_impl_generics rabbithole::entity::FromResource<L> for #decorated #ty_generics #lookup_where_clause {
+            async fn from_resource(resource: &rabbithole::model::resource::Resource, lookup: &L) -> rabbithole::Result<Self> {
+                #from_resource
+            }
+        }
+
+        impl #impl_generics rabbithole::validation::Validate for #decorated #ty_generics #where_clause {
+            fn validate(resource: &rabbithole::model::resource::Resource, partial: bool) -> std::result::Result<(), rabbithole::model::error::Errors> {
+                #validate
+            }
+        }
+
+        #patch
+    })
+}
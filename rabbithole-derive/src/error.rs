@@ -1,8 +1,8 @@
 #[derive(Error, Debug)]
 pub enum EntityDecoratorError {
     #[error(
-        "`EntityDecorator` macro can only be used on Named Structs with `id` decorator, just like \
-         `#[entity(type = \"foo_type\")]`"
+        "`EntityDecorator` macro can only be used on Named Structs with `id` decorator, newtypes \
+         of entities, or enums of them, with the type just like `#[entity(type = \"foo_type\")]`"
     )]
     InvalidEntityType,
     #[error(
@@ -12,8 +12,35 @@ pub enum EntityDecoratorError {
     LackOfService,
     #[error("Duplicated Id fields detected")]
     DuplicatedId,
-    #[error("Invalid unit decorator {0}, the valid ones: [id, to_one, to_many]")]
+    #[error(
+        "Invalid unit decorator {0}, the valid ones: [id, to_one, to_many, to_one(type = \"..\", \
+         linkage = \"..\"), to_many(type = \"..\", linkage = \"..\"), skip, read_only, \
+         write_only, meta, count, default = \"path\"], where `linkage` is one of [always, \
+         when_included, never]"
+    )]
     InvalidUnitDecorator(String),
+    #[error(
+        "Each variant of an entity enum should wrap a single entity, just like `Image(Image)`"
+    )]
+    InvalidVariant,
     #[error("Field without name")]
     FieldWithoutName,
+    #[error(
+        "Invalid validate decorator {0}, the valid ones: [required, length(min = .., max = ..), \
+         range(min = .., max = ..), regex = \"..\"]"
+    )]
+    InvalidValidateDecorator(String),
+    #[error("Invalid regex {0} in the validate decorator: {1}")]
+    InvalidRegex(String, String),
+    #[error(
+        "Invalid rename decorator {0}, the valid ones: [rename = \"..\", rename_all = \"..\"], \
+         where `rename_all` is one of [lowercase, UPPERCASE, PascalCase, camelCase, snake_case, \
+         SCREAMING_SNAKE_CASE, kebab-case, SCREAMING-KEBAB-CASE]"
+    )]
+    InvalidRenameDecorator(String),
+    #[error(
+        "Invalid decorator {0}, the valid ones: [meta(name = \"method\"), links(name = \
+         \"method\")]"
+    )]
+    InvalidExtraDecorator(String),
 }
@@ -0,0 +1,62 @@
+use crate::error::EntityDecoratorError;
+use crate::{get_decorators, get_meta};
+use quote::ToTokens;
+
+fn invalid(tokens: &impl ToTokens) -> syn::Error {
+    syn::Error::new_spanned(
+        tokens,
+        EntityDecoratorError::InvalidExtraDecorator(tokens.to_token_stream().to_string()),
+    )
+}
+
+/// If the decorator `meta` is `count`
+pub(crate) fn is_count(meta: &syn::Meta) -> bool {
+    if let syn::Meta::Path(path) = meta {
+        return path.is_ident("count");
+    }
+    false
+}
+
+/// If `field` is decorated by `#[entity(count)]`, which puts the number of the related resources
+/// into the `meta` of a to-many relationship
+pub(crate) fn has_count(field: &syn::Field) -> syn::Result<bool> {
+    Ok(get_decorators(&field.attrs)?.iter().any(is_count))
+}
+
+/// The methods decorated on `ast` by `#[entity(<key>(name = "method"))]` with their names, where
+/// `key` is `meta` or `links`
+pub(crate) fn get_methods(
+    ast: &syn::DeriveInput, key: &str,
+) -> syn::Result<(Vec<String>, Vec<syn::Ident>)> {
+    let mut names = vec![];
+    let mut methods = vec![];
+    for meta in get_meta(&ast.attrs)? {
+        if let syn::Meta::List(syn::MetaList { ref nested, .. }) = meta {
+            if let Some(syn::NestedMeta::Meta(syn::Meta::List(syn::MetaList {
+                path,
+                nested,
+                ..
+            }))) = nested.last()
+            {
+                if !path.is_ident(key) {
+                    continue;
+                }
+                for item in nested {
+                    match item {
+                        syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                            path,
+                            lit: syn::Lit::Str(lit_str),
+                            ..
+                        })) => {
+                            let name = path.get_ident().ok_or_else(|| invalid(path))?;
+                            names.push(name.to_string());
+                            methods.push(lit_str.parse::<syn::Ident>()?);
+                        },
+                        _ => return Err(invalid(item)),
+                    }
+                }
+            }
+        }
+    }
+    Ok((names, methods))
+}
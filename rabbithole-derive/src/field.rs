@@ -1,59 +1,289 @@
 use crate::error::EntityDecoratorError;
-use crate::get_meta;
-use quote::ToTokens;
+use crate::extra::is_count;
+use crate::get_decorators;
+use crate::rename::is_rename;
+use quote::{format_ident, ToTokens};
 
 lazy_static! {
     static ref VALID_TO_ONE_WRAPPER: Vec<&'static str> = vec!["Option", "Box"];
     static ref VALID_TO_MANY_WRAPPER: Vec<&'static str> = vec!["Vec", "HashSet"];
+    static ref LINKAGES: Vec<&'static str> = vec!["always", "when_included", "never"];
+}
+
+/// The last decorator of `item` deciding its `FieldType`, like `to_one` of
+/// `#[entity(to_one, read_only)]`
+fn type_decorator(item: &syn::Field) -> syn::Result<Option<syn::Meta>> {
+    // The `validate(...)`, `rename = ".."`, access and `count` decorators are handled by
+    // `crate::validate`, `crate::rename`, `get_field_access` and `crate::extra`
+    Ok(get_decorators(&item.attrs)?.into_iter().rev().find(|meta| {
+        !is_validate(meta)
+            && !is_rename(meta, "rename")
+            && !is_rename(meta, "default")
+            && access(meta).is_none()
+            && !is_count(meta)
+    }))
 }
 
 pub(crate) fn get_field_type(item: &syn::Field) -> syn::Result<FieldType> {
-    if let Some(syn::Meta::List(syn::MetaList { ref nested, .. })) = get_meta(&item.attrs)?.last() {
-        if let Some(syn::NestedMeta::Meta(ref meta_item)) = nested.last() {
-            match meta_item {
-                syn::Meta::Path(syn::Path { segments, .. }) => {
-                    if let Some(seg) = segments.last() {
-                        let field_ty = &seg.ident;
-                        if field_ty == "id" {
-                            return Ok(FieldType::Id);
-                        } else if field_ty == "to_many" {
-                            return Ok(FieldType::ToMany);
-                        } else if field_ty == "to_one" {
-                            return Ok(FieldType::ToOne);
-                        } else {
-                            return Err(syn::Error::new_spanned(
-                                field_ty,
-                                EntityDecoratorError::InvalidUnitDecorator(field_ty.to_string()),
-                            ));
-                        }
+    if let Some(ref meta_item) = type_decorator(item)? {
+        match meta_item {
+            syn::Meta::Path(syn::Path { segments, .. }) => {
+                if let Some(seg) = segments.last() {
+                    let field_ty = &seg.ident;
+                    if field_ty == "id" {
+                        return Ok(FieldType::Id);
+                    } else if field_ty == "to_many" {
+                        return Ok(FieldType::ToMany);
+                    } else if field_ty == "to_one" {
+                        return Ok(FieldType::ToOne);
                     } else {
                         return Err(syn::Error::new_spanned(
-                            meta_item,
-                            EntityDecoratorError::InvalidUnitDecorator(
-                                meta_item.path().segments.to_token_stream().to_string(),
-                            ),
+                            field_ty,
+                            EntityDecoratorError::InvalidUnitDecorator(field_ty.to_string()),
                         ));
                     }
-                },
-                _ => {
+                } else {
                     return Err(syn::Error::new_spanned(
                         meta_item,
                         EntityDecoratorError::InvalidUnitDecorator(
                             meta_item.path().segments.to_token_stream().to_string(),
                         ),
                     ));
-                },
-            }
+                }
+            },
+            // `to_one(..)` and `to_many(..)` with the options, where the ones with
+            // `type = ".."` are on the id fields
+            syn::Meta::List(syn::MetaList { path, nested, .. })
+                if path.is_ident("to_one") || path.is_ident("to_many") =>
+            {
+                let (ty, _) = relat_options(meta_item, nested)?;
+                return Ok(match (ty, path.is_ident("to_one")) {
+                    (Some(ty), true) => FieldType::LazyToOne(ty),
+                    (Some(ty), false) => FieldType::LazyToMany(ty),
+                    (None, true) => FieldType::ToOne,
+                    (None, false) => FieldType::ToMany,
+                });
+            },
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    meta_item,
+                    EntityDecoratorError::InvalidUnitDecorator(
+                        meta_item.path().segments.to_token_stream().to_string(),
+                    ),
+                ));
+            },
         }
     }
 
     Ok(FieldType::Plain)
 }
 
+/// The `type = ".."` and `linkage = ".."` options of `to_one(..)` or `to_many(..)`
+fn relat_options(
+    meta_item: &syn::Meta, nested: &syn::punctuated::Punctuated<syn::NestedMeta, syn::Token![,]>,
+) -> syn::Result<(Option<String>, Option<String>)> {
+    let invalid = || {
+        syn::Error::new_spanned(
+            meta_item,
+            EntityDecoratorError::InvalidUnitDecorator(meta_item.to_token_stream().to_string()),
+        )
+    };
+    let (mut ty, mut linkage) = (None, None);
+    for item in nested {
+        match item {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                path,
+                lit: syn::Lit::Str(lit_str),
+                ..
+            })) if path.is_ident("type") && ty.is_none() => ty = Some(lit_str.value()),
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                path,
+                lit: syn::Lit::Str(lit_str),
+                ..
+            })) if path.is_ident("linkage")
+                && linkage.is_none()
+                && LINKAGES.contains(&lit_str.value().as_str()) =>
+            {
+                linkage = Some(lit_str.value())
+            },
+            _ => return Err(invalid()),
+        }
+    }
+    if nested.is_empty() {
+        return Err(invalid());
+    }
+    Ok((ty, linkage))
+}
+
+/// The variant of `rabbithole::model::relationship::Linkage` decorated on the relationship
+/// `item` by `linkage = ".."`, which is `Always` by default
+pub(crate) fn get_linkage(item: &syn::Field) -> syn::Result<syn::Ident> {
+    let mut linkage = None;
+    if let Some(ref meta_item) = type_decorator(item)? {
+        if let syn::Meta::List(syn::MetaList { nested, .. }) = meta_item {
+            linkage = relat_options(meta_item, nested)?.1;
+        }
+    }
+    Ok(match linkage.as_deref() {
+        Some("never") => format_ident!("Never"),
+        Some("when_included") => format_ident!("WhenIncluded"),
+        _ => format_ident!("Always"),
+    })
+}
+
+/// The related type of a relationship holding only the ids, like `#[entity(to_one(type = "people"))]`
+pub(crate) fn get_lazy_type(item: &syn::Field) -> syn::Result<String> {
+    match get_field_type(item)? {
+        FieldType::LazyToOne(ty) | FieldType::LazyToMany(ty) => Ok(ty),
+        _ => unreachable!("only called on the lazy relationships"),
+    }
+}
+
+/// If `ty` is like `Option<T>`
+pub(crate) fn is_option(ty: &syn::Type) -> bool {
+    if let syn::Type::Path(syn::TypePath { path, .. }) = ty {
+        if let Some(seg) = path.segments.last() {
+            return seg.ident == "Option";
+        }
+    }
+    false
+}
+
+/// The first type argument of `ty`, like `T` of `Vec<T>`
+pub(crate) fn inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    if let syn::Type::Path(syn::TypePath { path, .. }) = ty {
+        if let Some(syn::PathArguments::AngleBracketed(args)) =
+            path.segments.last().map(|seg| &seg.arguments)
+        {
+            return args.args.iter().find_map(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            });
+        }
+    }
+    None
+}
+
+/// If the decorator `meta` is like `validate(...)`
+pub(crate) fn is_validate(meta: &syn::Meta) -> bool {
+    if let syn::Meta::List(syn::MetaList { path, .. }) = meta {
+        return path.is_ident("validate");
+    }
+    false
+}
+
+/// The access decorator `meta` is, like `read_only`, where `meta` renders a read-only field in
+/// the `meta` of the resource rather than the attributes. It can be listed with the other
+/// decorators, like `#[entity(to_many, read_only, default = "path")]`
+fn access(meta: &syn::Meta) -> Option<&'static str> {
+    if let syn::Meta::Path(path) = meta {
+        return ["skip", "read_only", "write_only", "meta"]
+            .iter()
+            .find(|access| path.is_ident(access))
+            .cloned();
+    }
+    None
+}
+
+/// How a field is accessed by the clients, decorated by `#[entity(skip)]`,
+/// `#[entity(read_only)]` and `#[entity(write_only)]`, or the `skip`, `skip_serializing` and
+/// `skip_deserializing` of serde
+pub(crate) fn get_field_access(item: &syn::Field) -> syn::Result<FieldAccess> {
+    let mut decorators: Vec<String> = get_decorators(&item.attrs)?
+        .iter()
+        .filter_map(access)
+        .map(ToString::to_string)
+        .collect();
+    for attr in item.attrs.iter().filter(|a| a.path.is_ident("serde")) {
+        if let Ok(syn::Meta::List(syn::MetaList { nested, .. })) = attr.parse_meta() {
+            for item in nested {
+                if let syn::NestedMeta::Meta(syn::Meta::Path(path)) = item {
+                    if let Some(ident) = path.get_ident() {
+                        decorators.push(ident.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    let has = |names: &[&str]| decorators.iter().any(|d| names.contains(&d.as_str()));
+    let readable = !has(&["skip", "write_only", "skip_serializing"]);
+    let writable = !has(&["skip", "read_only", "meta", "skip_deserializing"]);
+    Ok(match (readable, writable) {
+        (true, true) => FieldAccess::ReadWrite,
+        (true, false) => FieldAccess::ReadOnly,
+        (false, true) => FieldAccess::WriteOnly,
+        (false, false) => FieldAccess::Skip,
+    })
+}
+
+/// The function filling the field `item` when it is never read from the request bodies, decorated
+/// by `#[entity(default = "path")]`, or following `#[serde(default = "path")]`
+pub(crate) fn get_default(item: &syn::Field) -> syn::Result<Option<syn::ExprPath>> {
+    let attrs = item
+        .attrs
+        .iter()
+        .filter(|a| a.path.is_ident("entity") || a.path.is_ident("serde"));
+    for attr in attrs {
+        if let Ok(syn::Meta::List(syn::MetaList { nested, .. })) = attr.parse_meta() {
+            for meta in nested {
+                if let syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(lit_str),
+                    ..
+                })) = meta
+                {
+                    if path.is_ident("default") {
+                        return lit_str.parse().map(Some).map_err(|_| {
+                            syn::Error::new_spanned(
+                                &lit_str,
+                                EntityDecoratorError::InvalidUnitDecorator(lit_str.value()),
+                            )
+                        });
+                    }
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// If `item` is decorated by `#[entity(meta)]`
+pub(crate) fn is_meta_field(item: &syn::Field) -> syn::Result<bool> {
+    Ok(get_decorators(&item.attrs)?
+        .iter()
+        .any(|meta| access(meta) == Some("meta")))
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum FieldAccess {
+    ReadWrite,
+    /// Rendered in the documents, but rejected in the request bodies
+    ReadOnly,
+    /// Accepted in the request bodies, but never rendered
+    WriteOnly,
+    /// Neither an attribute nor a relationship
+    Skip,
+}
+
+impl FieldAccess {
+    pub(crate) fn readable(self) -> bool {
+        self == FieldAccess::ReadWrite || self == FieldAccess::ReadOnly
+    }
+
+    pub(crate) fn writable(self) -> bool {
+        self == FieldAccess::ReadWrite || self == FieldAccess::WriteOnly
+    }
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub(crate) enum FieldType {
     Id,
     ToOne,
     ToMany,
+    /// A to-one relationship holding the id of the related resource rather than the entity
+    LazyToOne(String),
+    /// A to-many relationship holding the ids of the related resources rather than the entities
+    LazyToMany(String),
     Plain,
 }
@@ -0,0 +1,138 @@
+use crate::field::{get_default, get_lazy_type, inner_type, is_option};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+
+/// All of the named fields of `ast`
+pub fn named_fields(ast: &syn::DeriveInput) -> Vec<&syn::Field> {
+    if let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(syn::FieldsNamed { ref named, .. }),
+        ..
+    }) = ast.data
+    {
+        named.iter().collect()
+    } else {
+        vec![]
+    }
+}
+
+/// The field named `ident` in `ast`
+pub fn field<'a>(ast: &'a syn::DeriveInput, ident: &syn::Ident) -> &'a syn::Field {
+    named_fields(ast)
+        .into_iter()
+        .find(|f| f.ident.as_ref() == Some(ident))
+        .expect("the fields are collected from `ast`")
+}
+
+/// The type of the field named `ident` in `ast`
+pub fn field_type<'a>(ast: &'a syn::DeriveInput, ident: &syn::Ident) -> &'a syn::Type {
+    &field(ast, ident).ty
+}
+
+/// Reads the ids of the lazy relationship `ident` named `name` from `resource`
+pub fn lazy_from_resource(
+    ast: &syn::DeriveInput, ident: &syn::Ident, name: &str, to_one: bool,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let field = field(ast, ident);
+    let ty = get_lazy_type(field)?;
+    Ok(if !to_one {
+        let id_ty = match inner_type(&field.ty) {
+            Some(id_ty) => quote!(#id_ty),
+            None => quote!(_),
+        };
+        quote!(rabbithole::entity::lazy_to_many_from_resource::<_, #id_ty>(resource, #name, #ty))
+    } else if is_option(&field.ty) {
+        quote!(rabbithole::entity::lazy_optional_to_one_from_resource(resource, #name, #ty))
+    } else {
+        quote!(rabbithole::entity::lazy_to_one_from_resource(resource, #name, #ty))
+    })
+}
+
+/// The lazy relationships `lazy_to_ones` and `lazy_to_manys` with the ways to read them
+pub fn lazy_relats<'a>(
+    ast: &syn::DeriveInput, lazy_to_ones: &[&'a syn::Ident], lazy_to_manys: &[&'a syn::Ident],
+) -> syn::Result<(
+    Vec<&'a syn::Ident>,
+    Vec<String>,
+    Vec<proc_macro2::TokenStream>,
+)> {
+    let lazy_relats: Vec<&syn::Ident> = lazy_to_ones.iter().chain(lazy_to_manys).cloned().collect();
+    let names = crate::rename::wire_names(ast, &lazy_relats)?;
+    let mut reads = vec![];
+    for (ident, name) in lazy_relats.iter().zip(&names) {
+        reads.push(lazy_from_resource(
+            ast,
+            ident,
+            name,
+            lazy_to_ones.contains(ident),
+        )?);
+    }
+    Ok((lazy_relats, names, reads))
+}
+
+pub fn generate_from_resource(
+    ast: &syn::DeriveInput, id: &syn::Ident, attrs: &[&syn::Ident], to_ones: &[&syn::Ident],
+    to_manys: &[&syn::Ident], lazy_to_ones: &[&syn::Ident], lazy_to_manys: &[&syn::Ident],
+) -> syn::Result<proc_macro2::TokenStream> {
+    let decorated_struct = &ast.ident;
+    let id_ty = field_type(ast, id);
+    let attr_tys: Vec<&syn::Type> = attrs.iter().map(|f| field_type(ast, f)).collect();
+    let relats: Vec<&syn::Ident> = to_ones.iter().chain(to_manys.iter()).cloned().collect();
+    let relat_tys: Vec<&syn::Type> = relats.iter().map(|f| field_type(ast, f)).collect();
+    let attr_names = crate::rename::wire_names(ast, attrs)?;
+    let relat_names = crate::rename::wire_names(ast, &relats)?;
+    let (lazy_relats, _, lazy_reads) = lazy_relats(ast, lazy_to_ones, lazy_to_manys)?;
+    // The skipped and read-only fields are never read from the request bodies
+    let defaults: Vec<&syn::Field> = named_fields(ast)
+        .into_iter()
+        .filter(|f| match f.ident.as_ref() {
+            Some(ident) => {
+                ident != id
+                    && !attrs.contains(&ident)
+                    && !relats.contains(&ident)
+                    && !lazy_relats.contains(&ident)
+            },
+            None => false,
+        })
+        .collect();
+    let default_idents: Vec<&syn::Ident> =
+        defaults.iter().filter_map(|f| f.ident.as_ref()).collect();
+    // Spanned on the field, so a type without `Default` and `default = "path"` is reported there
+    let mut default_values = vec![];
+    for field in &defaults {
+        let ty = &field.ty;
+        default_values.push(match get_default(field)? {
+            Some(path) => quote!(#path()),
+            None => quote_spanned!(ty.span()=> <#ty as std::default::Default>::default()),
+        });
+    }
+
+    let mut generics = ast.generics.clone();
+    generics.params.push(syn::parse_quote!(L));
+    let predicates = &mut generics.make_where_clause().predicates;
+    predicates.push(syn::parse_quote!(L: std::marker::Sync));
+    predicates.push(syn::parse_quote!(#id_ty: std::str::FromStr));
+    predicates.push(syn::parse_quote!(<#id_ty as std::str::FromStr>::Err: std::fmt::Display));
+    for attr_ty in attr_tys {
+        predicates.push(syn::parse_quote!(#attr_ty: serde::de::DeserializeOwned));
+    }
+    for relat_ty in relat_tys {
+        predicates.push(syn::parse_quote!(L: rabbithole::entity::RelationshipLookup<#relat_ty>));
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+
+    Ok(quote! {
+        #[rabbithole::async_trait]
+        impl #impl_generics rabbithole::entity::FromResource<L> for #decorated_struct #ty_generics #where_clause {
+            async fn from_resource(resource: &rabbithole::model::resource::Resource, lookup: &L) -> rabbithole::Result<Self> {
+                Ok(Self {
+                    #id: rabbithole::entity::id_from_resource(resource)?,
+                    #( #attrs: rabbithole::entity::attribute_from_resource(resource, #attr_names)?, )*
+                    #( #relats: rabbithole::entity::relationship_from_resource(resource, #relat_names, lookup).await?, )*
+                    #( #lazy_relats: #lazy_reads?, )*
+                    #( #default_idents: #default_values, )*
+                })
+            }
+        }
+    })
+}
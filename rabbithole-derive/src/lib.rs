@@ -5,21 +5,32 @@ extern crate thiserror;
 extern crate lazy_static;
 
 mod backend;
+mod delegate;
 mod error;
+mod extra;
 mod field;
+mod from_resource;
+mod patch;
+mod relationship;
+mod rename;
+mod validate;
 
 use crate::error::EntityDecoratorError;
-use crate::field::{get_field_type, FieldType};
+use crate::field::{get_field_access, get_field_type, is_meta_field, FieldAccess, FieldType};
 use proc_macro::TokenStream;
 use quote::{quote, TokenStreamExt};
 use std::collections::HashSet;
 use syn::DeriveInput;
 
+/// The id, attributes, to-one and to-many relationships, and the relationships holding only
+/// the ids of the related resources
 type FieldBundle<'a> = (
     &'a syn::Ident,
     Vec<&'a syn::Ident>,
     Vec<&'a syn::Ident>,
     Vec<&'a syn::Ident>,
+    Vec<&'a syn::Ident>,
+    Vec<&'a syn::Ident>,
 );
 
 #[proc_macro_derive(EntityDecorator, attributes(entity))]
@@ -35,52 +46,179 @@ fn inner_derive(input: TokenStream) -> syn::Result<proc_macro2::TokenStream> {
     let decorated_struct: &syn::Ident = &ast.ident;
     let struct_lifetime = &ast.generics;
 
+    if let Some(delegate) = delegate::get_delegate(&ast)? {
+        let (entity_type, backends, service) = get_entity_attrs(&ast)?;
+        let mut res = delegate::generate_delegate(&ast, &delegate, entity_type.as_deref())?;
+        if let (Some(entity_type), Some(service)) = (entity_type, service) {
+            // The enums are not `Patchable`, so they are not routed to `update_resource`
+            let patchable = matches!(delegate, delegate::Delegate::Newtype(_));
+            res.append_all(generate_backends(
+                &backends,
+                &service,
+                &entity_type,
+                patchable,
+            ));
+        }
+        return Ok(res);
+    }
+
     let (entity_type, backends, service) = get_entity_type(&ast)?;
 
-    let (id, attrs, to_ones, to_manys) = get_fields(&ast)?;
+    let (id, all_attrs, all_to_ones, all_to_manys, all_lazy_to_ones, all_lazy_to_manys) =
+        get_fields(&ast)?;
+    let (attrs, to_ones, to_manys, lazy_to_ones, lazy_to_manys) = (
+        filter_fields(&ast, &all_attrs, FieldAccess::readable)?,
+        filter_fields(&ast, &all_to_ones, FieldAccess::readable)?,
+        filter_fields(&ast, &all_to_manys, FieldAccess::readable)?,
+        filter_fields(&ast, &all_lazy_to_ones, FieldAccess::readable)?,
+        filter_fields(&ast, &all_lazy_to_manys, FieldAccess::readable)?,
+    );
+    let (writable_attrs, writable_to_ones, writable_to_manys) = (
+        filter_fields(&ast, &all_attrs, FieldAccess::writable)?,
+        filter_fields(&ast, &all_to_ones, FieldAccess::writable)?,
+        filter_fields(&ast, &all_to_manys, FieldAccess::writable)?,
+    );
+    let (writable_lazy_to_ones, writable_lazy_to_manys) = (
+        filter_fields(&ast, &all_lazy_to_ones, FieldAccess::writable)?,
+        filter_fields(&ast, &all_lazy_to_manys, FieldAccess::writable)?,
+    );
+    // The readable fields decorated by `#[entity(meta)]` are rendered in the `meta`
+    let mut meta_fields = vec![];
+    for ident in attrs.iter() {
+        if is_meta_field(from_resource::field(&ast, ident))? {
+            meta_fields.push(*ident);
+        }
+    }
+    let attrs: Vec<&syn::Ident> = attrs
+        .into_iter()
+        .filter(|ident| !meta_fields.contains(ident))
+        .collect();
+    let meta_names = rename::wire_names(&ast, &meta_fields)?;
+    let (meta_keys, meta_methods) = extra::get_methods(&ast, "meta")?;
+    let meta_keys = rename::renamed_all(&ast, meta_keys)?;
+    let (link_names, link_methods) = extra::get_methods(&ast, "links")?;
+    let to_many_metas = count_metas(&ast, &to_manys)?;
+    let lazy_to_many_metas = count_metas(&ast, &lazy_to_manys)?;
+    for ident in all_attrs
+        .iter()
+        .chain(&all_to_ones)
+        .chain(&all_lazy_to_ones)
+    {
+        let field = from_resource::field(&ast, ident);
+        if extra::has_count(field)? {
+            return Err(syn::Error::new_spanned(
+                field,
+                EntityDecoratorError::InvalidUnitDecorator("count".to_string()),
+            ));
+        }
+    }
+    let relats: Vec<&syn::Ident> = to_ones
+        .iter()
+        .chain(&to_manys)
+        .chain(&lazy_to_ones)
+        .chain(&lazy_to_manys)
+        .cloned()
+        .collect();
+    let (linkage_names, linkages) = get_linkages(&ast, &relats)?;
+    let all_fields: Vec<&syn::Ident> = all_attrs
+        .iter()
+        .chain(&all_to_ones)
+        .chain(&all_to_manys)
+        .chain(&all_lazy_to_ones)
+        .chain(&all_lazy_to_manys)
+        .cloned()
+        .collect();
+    let read_only_names = rename::wire_names(
+        &ast,
+        &filter_fields(&ast, &all_fields, |access| access == FieldAccess::ReadOnly)?,
+    )?;
+    let is_writable = if read_only_names.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            fn is_writable(field: &str) -> bool {
+                match field {
+                    #( #read_only_names )|* => false,
+                    _ => true,
+                }
+            }
+        }
+    };
+    let id_ty = from_resource::field_type(&ast, id);
+    let attr_names = rename::wire_names(&ast, &attrs)?;
+    let to_one_names = rename::wire_names(&ast, &to_ones)?;
+    let to_many_names = rename::wire_names(&ast, &to_manys)?;
+    let lazy_to_one_names = rename::wire_names(&ast, &lazy_to_ones)?;
+    let lazy_to_many_names = rename::wire_names(&ast, &lazy_to_manys)?;
+    let lazy_to_one_tys = lazy_types(&ast, &lazy_to_ones)?;
+    let lazy_to_many_tys = lazy_types(&ast, &lazy_to_manys)?;
+    // The id of a lazy to-one relationship, as an `Option<&Id>`
+    let lazy_to_one_ids: Vec<proc_macro2::TokenStream> = lazy_to_ones
+        .iter()
+        .map(|f| {
+            if field::is_option(from_resource::field_type(&ast, f)) {
+                quote!(self.#f.as_ref())
+            } else {
+                quote!(std::option::Option::Some(&self.#f))
+            }
+        })
+        .collect();
+
+    // The related resources of the lazy relationships are loaded by the services only
+    let lazy_names: Vec<&String> = lazy_to_one_names
+        .iter()
+        .chain(&lazy_to_many_names)
+        .collect();
+    let lazy_related = if lazy_names.is_empty() {
+        quote!()
+    } else {
+        quote!(#( #lazy_names )|* => Err(rabbithole::model::error::Error::LazyRelatedNotImplemented(field, None)),)
+    };
+    let relat_names: Vec<String> = to_one_names
+        .iter()
+        .chain(&to_many_names)
+        .chain(&lazy_to_one_names)
+        .chain(&lazy_to_many_names)
+        .cloned()
+        .collect();
 
     let mut res = quote! {
         impl #struct_lifetime rabbithole::entity::Entity for #decorated_struct#struct_lifetime {
             fn included(&self, uri: &str,
                 include_query: &std::option::Option<rabbithole::query::IncludeQuery>,
                 fields_query: &rabbithole::query::FieldsQuery,
+                visibility: &dyn rabbithole::policy::FieldVisibility,
             ) -> rabbithole::Result<rabbithole::model::document::Included> {
                 use rabbithole::entity::SingleEntity;
                 use std::convert::TryInto;
                 let mut included: rabbithole::model::document::Included = Default::default();
+                let ty = <Self as SingleEntity>::ty();
 
                 if let Some(included_fields) = include_query {
                     for inc in included_fields {
-                        if inc.contains('.') {
-                            return Err(rabbithole::model::error::Error::RelationshipPathNotSupported(&inc, None));
+                        // The relationships never linked cannot be included either
+                        if inc.contains('.') || self.linkage(inc) == rabbithole::model::relationship::Linkage::Never {
+                            return Err(rabbithole::model::error::Error::RelationshipPathNotSupported(&inc, Some(rabbithole::model::error::ErrorSource::from_parameter("include"))));
                         }
                     }
                 }
+                // Without the `include` query, only the relationships always linked are included
+                #[allow(unused_variables)]
+                let is_included = |field: &str| match include_query {
+                    Some(included_fields) => included_fields.contains(field),
+                    None => self.linkage(field) == rabbithole::model::relationship::Linkage::Always,
+                };
                 #(
-                    if let Some(included_fields) = include_query {
-                        if included_fields.contains(stringify!(#to_ones)) {
-                            if let Some(inc) = self.#to_ones.to_resource(uri, fields_query) {
-                                included.insert(inc.id.clone(), inc);
-                            }
-                        }
-                    } else {
-                        if let Some(inc) = self.#to_ones.to_resource(uri, fields_query) {
+                    if visibility.can_read_field(&ty, #to_one_names) && is_included(#to_one_names) {
+                        if let Some(inc) = self.#to_ones.to_resource(uri, fields_query, visibility) {
                             included.insert(inc.id.clone(), inc);
                         }
                     }
                 )*
                 #(
-                    if let Some(included_fields) = include_query {
-                        if included_fields.contains(stringify!(#to_manys)) {
-                            for item in &self.#to_manys {
-                                if let Some(inc) = item.to_resource(uri, fields_query) {
-                                    included.insert(inc.id.clone(), inc);
-                                }
-                            }
-                        }
-                    } else {
+                    if visibility.can_read_field(&ty, #to_many_names) && is_included(#to_many_names) {
                         for item in &self.#to_manys {
-                            if let Some(inc) = item.to_resource(uri, fields_query) {
+                            if let Some(inc) = item.to_resource(uri, fields_query, visibility) {
                                 included.insert(inc.id.clone(), inc);
                             }
                         }
@@ -95,23 +233,24 @@ fn inner_derive(input: TokenStream) -> syn::Result<proc_macro2::TokenStream> {
         }
 
         impl #struct_lifetime rabbithole::entity::SingleEntity for #decorated_struct#struct_lifetime {
+            type Id = #id_ty;
+
             fn ty() -> std::string::String { #entity_type.to_string() }
             fn id(&self) -> std::string::String { self.#id.to_string() }
+            fn parse_id(id: &str) -> rabbithole::Result<Self::Id> { rabbithole::entity::id_from_path(id) }
 
             fn attributes(&self) -> rabbithole::model::resource::Attributes {
                 let mut attr_map: std::collections::HashMap<String, serde_json::Value> = std::default::Default::default();
-                #(  if let Ok(json_value) = serde_json::to_value(self.#attrs.clone()) { attr_map.insert(stringify!(#attrs).to_string(), json_value); } )*
+                #(  attr_map.insert(#attr_names.to_string(), rabbithole::entity::field_value(&self.#attrs, #entity_type, #attr_names)); )*
                 attr_map.into()
             }
 
             fn relationships(&self, uri: &str) -> rabbithole::model::relationship::Relationships {
                 let mut relat_map: rabbithole::model::relationship::Relationships = std::default::Default::default();
                 #(
-                    if let Some(relat_id) = self.#to_ones.to_resource_identifier() {
-                        let data = rabbithole::model::resource::IdentifierData::Single(Some(relat_id));
-                        let relat = rabbithole::model::relationship::Relationship { data, links: self.to_relationship_links(stringify!(#to_ones), uri), ..std::default::Default::default() };
-                        relat_map.insert(stringify!(#to_ones).to_string(), relat);
-                    }
+                    let data = rabbithole::model::resource::IdentifierData::Single(self.#to_ones.to_resource_identifier());
+                    let relat = rabbithole::model::relationship::Relationship { data: Some(data), links: self.to_relationship_links(#to_one_names, uri), ..std::default::Default::default() };
+                    relat_map.insert(#to_one_names.to_string(), relat);
                 )*
 
                 #(
@@ -121,27 +260,113 @@ fn inner_derive(input: TokenStream) -> syn::Result<proc_macro2::TokenStream> {
                             relat_ids.push(relat_id);
                         }
                     }
+                    let meta: rabbithole::model::Meta = #to_many_metas;
+                    let data = rabbithole::model::resource::IdentifierData::Multiple(relat_ids);
+                    let relat = rabbithole::model::relationship::Relationship { data: Some(data), links: self.to_relationship_links(#to_many_names, uri), meta };
+                    relat_map.insert(#to_many_names.to_string(), relat);
+                )*
+
+                #(
+                    let data = rabbithole::model::resource::IdentifierData::Single(#lazy_to_one_ids.map(|relat_id| rabbithole::entity::lazy_identifier(#lazy_to_one_tys, relat_id)));
+                    let relat = rabbithole::model::relationship::Relationship { data: Some(data), links: self.to_relationship_links(#lazy_to_one_names, uri), ..std::default::Default::default() };
+                    relat_map.insert(#lazy_to_one_names.to_string(), relat);
+                )*
+
+                #(
+                    let relat_ids: Vec<rabbithole::model::resource::ResourceIdentifier> = self.#lazy_to_manys.iter().map(|relat_id| rabbithole::entity::lazy_identifier(#lazy_to_many_tys, relat_id)).collect();
+                    let meta: rabbithole::model::Meta = #lazy_to_many_metas;
                     let data = rabbithole::model::resource::IdentifierData::Multiple(relat_ids);
-                    let relat = rabbithole::model::relationship::Relationship { data, links: self.to_relationship_links(stringify!(#to_manys), uri), ..std::default::Default::default() };
-                    relat_map.insert(stringify!(#to_manys).to_string(), relat);
+                    let relat = rabbithole::model::relationship::Relationship { data: Some(data), links: self.to_relationship_links(#lazy_to_many_names, uri), meta };
+                    relat_map.insert(#lazy_to_many_names.to_string(), relat);
                 )*
 
                 relat_map
             }
+
+            fn links(&self, uri: &str) -> rabbithole::model::link::Links {
+                #[allow(unused_mut)]
+                let mut links = rabbithole::entity::resource_links(&<Self as rabbithole::entity::SingleEntity>::ty(), &rabbithole::entity::SingleEntity::id(self), uri);
+                #( links.insert(#link_names.to_string(), self.#link_methods(uri).into()); )*
+                links
+            }
+
+            #is_writable
+
+            fn linkage(&self, field: &str) -> rabbithole::model::relationship::Linkage {
+                match field {
+                    #( #linkage_names => rabbithole::model::relationship::Linkage::#linkages, )*
+                    _ => rabbithole::model::relationship::Linkage::Always,
+                }
+            }
+
+            fn related_document(&self, field: &str, uri: &str, query: &rabbithole::query::Query, request_path: http::Uri) -> rabbithole::Result<rabbithole::model::document::Document> {
+                match field {
+                    #( #to_one_names => rabbithole::entity::SingleEntity::to_document(&self.#to_ones, uri, query, request_path, std::default::Default::default(), std::default::Default::default()), )*
+                    #( #to_many_names => rabbithole::entity::Entity::to_document(&self.#to_manys.iter().collect::<Vec<_>>(), uri, query, request_path, std::default::Default::default(), std::default::Default::default()), )*
+                    #lazy_related
+                    _ => Err(rabbithole::model::error::Error::FieldNotExist(field, None)),
+                }
+            }
+
+            fn meta(&self) -> rabbithole::model::Meta {
+                #[allow(unused_mut)]
+                let mut meta: rabbithole::model::Meta = std::default::Default::default();
+                #(  meta.insert(#meta_names.to_string(), rabbithole::entity::field_value(&self.#meta_fields, #entity_type, #meta_names)); )*
+                #(  meta.insert(#meta_keys.to_string(), rabbithole::entity::field_value(&self.#meta_methods(), #entity_type, #meta_keys)); )*
+                meta
+            }
         }
 
 
     };
 
-    for back in backends {
-        if back == "actix" {
-            res.append_all(vec![backend::actix::generate_app(&service, &entity_type)]);
-        }
-    }
+    res.append_all(vec![from_resource::generate_from_resource(
+        &ast,
+        id,
+        &writable_attrs,
+        &writable_to_ones,
+        &writable_to_manys,
+        &writable_lazy_to_ones,
+        &writable_lazy_to_manys,
+    )?]);
+    let all_relats: Vec<&syn::Ident> = all_to_ones
+        .iter()
+        .chain(&all_to_manys)
+        .chain(&all_lazy_to_ones)
+        .chain(&all_lazy_to_manys)
+        .cloned()
+        .collect();
+    res.append_all(vec![validate::generate_validate(&ast, id, &all_relats)?]);
+    res.append_all(vec![patch::generate_patch(
+        &ast,
+        &writable_attrs,
+        &writable_to_ones,
+        &writable_to_manys,
+        &writable_lazy_to_ones,
+        &writable_lazy_to_manys,
+    )?]);
+
+    res.append_all(vec![relationship::generate_relationship(
+        &ast,
+        &relats,
+        &relat_names,
+    )]);
+
+    res.append_all(generate_backends(&backends, &service, &entity_type, true));
 
     Ok(res)
 }
 
+fn generate_backends(
+    backends: &HashSet<String>, service: &syn::Path, entity_type: &str, patchable: bool,
+) -> Vec<proc_macro2::TokenStream> {
+    backends
+        .iter()
+        .filter(|back| *back == "actix")
+        .map(|_| backend::actix::generate_app(service, entity_type, patchable))
+        .collect()
+}
+
 fn get_meta(attrs: &[syn::Attribute]) -> syn::Result<Vec<syn::Meta>> {
     Ok(attrs
         .iter()
@@ -153,7 +378,101 @@ fn get_meta(attrs: &[syn::Attribute]) -> syn::Result<Vec<syn::Meta>> {
         .collect::<Vec<syn::Meta>>())
 }
 
+/// The decorators listed in the `#[entity(..)]` of `attrs` one by one, like both `to_many` and
+/// `read_only` of `#[entity(to_many, read_only)]`
+fn get_decorators(attrs: &[syn::Attribute]) -> syn::Result<Vec<syn::Meta>> {
+    Ok(get_meta(attrs)?
+        .into_iter()
+        .filter_map(|meta| match meta {
+            syn::Meta::List(syn::MetaList { nested, .. }) => Some(nested),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|item| match item {
+            syn::NestedMeta::Meta(meta) => Some(meta),
+            syn::NestedMeta::Lit(_) => None,
+        })
+        .collect())
+}
+
+/// The fields in `idents` whose access matches `pred`
+fn filter_fields<'a>(
+    ast: &syn::DeriveInput, idents: &[&'a syn::Ident], pred: fn(FieldAccess) -> bool,
+) -> syn::Result<Vec<&'a syn::Ident>> {
+    let mut res = vec![];
+    for ident in idents {
+        if pred(get_field_access(from_resource::field(ast, ident))?) {
+            res.push(*ident);
+        }
+    }
+    Ok(res)
+}
+
+/// The `meta` of the to-many relationships `idents`, where the ones decorated by
+/// `#[entity(count)]` have the number of the ids in `relat_ids`
+fn count_metas(
+    ast: &syn::DeriveInput, idents: &[&syn::Ident],
+) -> syn::Result<Vec<proc_macro2::TokenStream>> {
+    let mut res = vec![];
+    for ident in idents {
+        res.push(if extra::has_count(from_resource::field(ast, ident))? {
+            quote!(rabbithole::entity::count_meta(relat_ids.len()))
+        } else {
+            quote!(std::default::Default::default())
+        });
+    }
+    Ok(res)
+}
+
+/// The names of the relationships `idents` whose `Linkage` is decorated, with the variants
+fn get_linkages(
+    ast: &syn::DeriveInput, idents: &[&syn::Ident],
+) -> syn::Result<(Vec<String>, Vec<syn::Ident>)> {
+    let names = rename::wire_names(ast, idents)?;
+    let mut res = (vec![], vec![]);
+    for (ident, name) in idents.iter().zip(names) {
+        let linkage = field::get_linkage(from_resource::field(ast, ident))?;
+        if linkage != "Always" {
+            res.0.push(name);
+            res.1.push(linkage);
+        }
+    }
+    Ok(res)
+}
+
+/// The related types of the lazy relationships `idents`
+fn lazy_types(ast: &syn::DeriveInput, idents: &[&syn::Ident]) -> syn::Result<Vec<String>> {
+    idents
+        .iter()
+        .map(|ident| field::get_lazy_type(from_resource::field(ast, ident)))
+        .collect()
+}
+
 fn get_entity_type(ast: &syn::DeriveInput) -> syn::Result<(String, HashSet<String>, syn::Path)> {
+    let (ty_opt, backends, service) = get_entity_attrs(ast)?;
+
+    if ty_opt.is_none() {
+        return Err(syn::Error::new_spanned(
+            ast,
+            EntityDecoratorError::InvalidEntityType,
+        ));
+    }
+
+    if service.is_none() {
+        return Err(syn::Error::new_spanned(
+            ast,
+            EntityDecoratorError::LackOfService,
+        ));
+    }
+
+    Ok((ty_opt.unwrap(), backends, service.unwrap()))
+}
+
+/// The `type`, backends and service decorated on `ast`, which are optional for the entities
+/// wrapping the other ones
+fn get_entity_attrs(
+    ast: &syn::DeriveInput,
+) -> syn::Result<(Option<String>, HashSet<String>, Option<syn::Path>)> {
     let mut ty_opt: Option<String> = None;
     let mut backends: HashSet<String> = Default::default();
     let mut service: Option<syn::Path> = None;
@@ -205,21 +524,7 @@ fn get_entity_type(ast: &syn::DeriveInput) -> syn::Result<(String, HashSet<Strin
         }
     }
 
-    if ty_opt.is_none() {
-        return Err(syn::Error::new_spanned(
-            ast,
-            EntityDecoratorError::InvalidEntityType,
-        ));
-    }
-
-    if service.is_none() {
-        return Err(syn::Error::new_spanned(
-            ast,
-            EntityDecoratorError::LackOfService,
-        ));
-    }
-
-    Ok((ty_opt.unwrap(), backends, service.unwrap()))
+    Ok((ty_opt, backends, service))
 }
 
 fn get_fields(ast: &syn::DeriveInput) -> syn::Result<FieldBundle> {
@@ -232,8 +537,13 @@ fn get_fields(ast: &syn::DeriveInput) -> syn::Result<FieldBundle> {
         let mut attrs = vec![];
         let mut to_ones = vec![];
         let mut to_manys = vec![];
+        let mut lazy_to_ones = vec![];
+        let mut lazy_to_manys = vec![];
 
         for n in named {
+            if get_field_access(n)? == FieldAccess::Skip {
+                continue;
+            }
             let f: FieldType = get_field_type(n)?;
             match (f, n.ident.as_ref()) {
                 (FieldType::Id, Some(ident)) if id.is_none() => id = Some(ident),
@@ -245,6 +555,8 @@ fn get_fields(ast: &syn::DeriveInput) -> syn::Result<FieldBundle> {
                 },
                 (FieldType::ToOne, Some(ident)) => to_ones.push(ident),
                 (FieldType::ToMany, Some(ident)) => to_manys.push(ident),
+                (FieldType::LazyToOne(_), Some(ident)) => lazy_to_ones.push(ident),
+                (FieldType::LazyToMany(_), Some(ident)) => lazy_to_manys.push(ident),
                 (FieldType::Plain, Some(ident)) => {
                     attrs.push(ident);
                 },
@@ -258,7 +570,7 @@ fn get_fields(ast: &syn::DeriveInput) -> syn::Result<FieldBundle> {
         }
 
         if let Some(id) = id {
-            return Ok((id, attrs, to_ones, to_manys));
+            return Ok((id, attrs, to_ones, to_manys, lazy_to_ones, lazy_to_manys));
         }
     }
     Err(syn::Error::new_spanned(
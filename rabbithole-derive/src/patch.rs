@@ -0,0 +1,82 @@
+use crate::from_resource::field_type;
+use quote::{format_ident, quote};
+
+pub fn generate_patch(
+    ast: &syn::DeriveInput, attrs: &[&syn::Ident], to_ones: &[&syn::Ident],
+    to_manys: &[&syn::Ident], lazy_to_ones: &[&syn::Ident], lazy_to_manys: &[&syn::Ident],
+) -> syn::Result<proc_macro2::TokenStream> {
+    let decorated_struct = &ast.ident;
+    let vis = &ast.vis;
+    let patch_struct = format_ident!("{}Patch", decorated_struct);
+    let attr_tys: Vec<&syn::Type> = attrs.iter().map(|f| field_type(ast, f)).collect();
+    let relats: Vec<&syn::Ident> = to_ones.iter().chain(to_manys.iter()).cloned().collect();
+    let relat_tys: Vec<&syn::Type> = relats.iter().map(|f| field_type(ast, f)).collect();
+    let (lazy_relats, lazy_names, lazy_reads) =
+        crate::from_resource::lazy_relats(ast, lazy_to_ones, lazy_to_manys)?;
+    let lazy_tys: Vec<&syn::Type> = lazy_relats.iter().map(|f| field_type(ast, f)).collect();
+    let fields: Vec<&syn::Ident> = attrs
+        .iter()
+        .chain(relats.iter())
+        .chain(lazy_relats.iter())
+        .cloned()
+        .collect();
+    let attr_names = crate::rename::wire_names(ast, attrs)?;
+    let relat_names = crate::rename::wire_names(ast, &relats)?;
+
+    let mut generics = ast.generics.clone();
+    generics.params.push(syn::parse_quote!(L));
+    let predicates = &mut generics.make_where_clause().predicates;
+    predicates.push(syn::parse_quote!(L: std::marker::Sync));
+    for attr_ty in &attr_tys {
+        predicates.push(syn::parse_quote!(#attr_ty: serde::de::DeserializeOwned));
+    }
+    for relat_ty in &relat_tys {
+        predicates.push(syn::parse_quote!(L: rabbithole::entity::RelationshipLookup<#relat_ty>));
+    }
+    let (lookup_impl_generics, _, lookup_where_clause) = generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let doc = format!(
+        "The partial update of `{}`, where a `None` field is left untouched",
+        decorated_struct
+    );
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis struct #patch_struct #ty_generics #where_clause {
+            #( pub #attrs: std::option::Option<#attr_tys>, )*
+            #( pub #relats: std::option::Option<#relat_tys>, )*
+            #( pub #lazy_relats: std::option::Option<#lazy_tys>, )*
+        }
+
+        impl #impl_generics std::default::Default for #patch_struct #ty_generics #where_clause {
+            fn default() -> Self {
+                Self { #( #fields: std::option::Option::None, )* }
+            }
+        }
+
+        impl #impl_generics rabbithole::entity::Patchable for #decorated_struct #ty_generics #where_clause {
+            type Patch = #patch_struct #ty_generics;
+
+            fn apply_patch(&mut self, patch: Self::Patch) {
+                #( if let std::option::Option::Some(value) = patch.#fields { self.#fields = value; } )*
+            }
+        }
+
+        #[rabbithole::async_trait]
+        impl #lookup_impl_generics rabbithole::entity::FromResource<L> for #patch_struct #ty_generics #lookup_where_clause {
+            async fn from_resource(resource: &rabbithole::model::resource::Resource, lookup: &L) -> rabbithole::Result<Self> {
+                Ok(Self {
+                    #( #attrs: rabbithole::entity::patch_attribute_from_resource(resource, #attr_names)?, )*
+                    #( #relats: rabbithole::entity::patch_relationship_from_resource(resource, #relat_names, lookup).await?, )*
+                    #(
+                        #lazy_relats: if resource.relationships.contains_key(#lazy_names) {
+                            std::option::Option::Some(#lazy_reads?)
+                        } else {
+                            std::option::Option::None
+                        },
+                    )*
+                })
+            }
+        }
+    })
+}
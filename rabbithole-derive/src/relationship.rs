@@ -0,0 +1,44 @@
+use quote::{format_ident, quote};
+
+/// Generates `<Entity>Relationship`, the enum of the readable relationships `relats` whose names
+/// in the documents are `names`, which is parsed from and displayed as these names
+pub fn generate_relationship(
+    ast: &syn::DeriveInput, relats: &[&syn::Ident], names: &[String],
+) -> proc_macro2::TokenStream {
+    let decorated_struct = &ast.ident;
+    let vis = &ast.vis;
+    let relat_enum = format_ident!("{}Relationship", decorated_struct);
+    let variants = crate::rename::variant_names(relats);
+    let doc = format!(
+        "The relationships of `{}`, parsed from their names in the routes",
+        decorated_struct
+    );
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+        #vis enum #relat_enum {
+            #( #variants, )*
+        }
+
+        impl std::str::FromStr for #relat_enum {
+            type Err = rabbithole::model::error::Error;
+
+            fn from_str(field: &str) -> std::result::Result<Self, Self::Err> {
+                match field {
+                    #( #names => Ok(Self::#variants), )*
+                    _ => Err(rabbithole::model::error::Error::FieldNotExist(field, None)),
+                }
+            }
+        }
+
+        impl std::fmt::Display for #relat_enum {
+            #[allow(unreachable_code)]
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(match *self {
+                    #( Self::#variants => #names, )*
+                })
+            }
+        }
+    }
+}
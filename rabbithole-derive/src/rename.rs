@@ -0,0 +1,149 @@
+use crate::error::EntityDecoratorError;
+use crate::get_decorators;
+use quote::ToTokens;
+use std::str::FromStr;
+
+/// The case conventions of `#[entity(rename_all = "...")]`, just like the ones of serde
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl FromStr for RenameRule {
+    type Err = ();
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        match rule {
+            "lowercase" => Ok(RenameRule::Lower),
+            "UPPERCASE" => Ok(RenameRule::Upper),
+            "PascalCase" => Ok(RenameRule::Pascal),
+            "camelCase" => Ok(RenameRule::Camel),
+            "snake_case" => Ok(RenameRule::Snake),
+            "SCREAMING_SNAKE_CASE" => Ok(RenameRule::ScreamingSnake),
+            "kebab-case" => Ok(RenameRule::Kebab),
+            "SCREAMING-KEBAB-CASE" => Ok(RenameRule::ScreamingKebab),
+            _ => Err(()),
+        }
+    }
+}
+
+impl RenameRule {
+    /// Applies the rule to a snake_case field name
+    fn apply(self, field: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => field.to_string(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal | RenameRule::Camel => {
+                let mut res = String::new();
+                let mut capitalize = self == RenameRule::Pascal;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        res.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        res.push(ch);
+                    }
+                }
+                res
+            },
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+fn invalid(tokens: &impl ToTokens) -> syn::Error {
+    syn::Error::new_spanned(
+        tokens,
+        EntityDecoratorError::InvalidRenameDecorator(tokens.to_token_stream().to_string()),
+    )
+}
+
+/// The value of `#[entity(<key> = "...")]` in `attrs`, if any
+fn get_rename(attrs: &[syn::Attribute], key: &str) -> syn::Result<Option<syn::LitStr>> {
+    let mut res = None;
+    for meta in get_decorators(attrs)?.iter().filter(|m| is_rename(m, key)) {
+        if let syn::Meta::NameValue(name_value) = meta {
+            match &name_value.lit {
+                syn::Lit::Str(lit_str) => res = Some(lit_str.clone()),
+                _ => return Err(invalid(name_value)),
+            }
+        }
+    }
+    Ok(res)
+}
+
+/// If the decorator `meta` is like `<key> = ...`
+pub(crate) fn is_rename(meta: &syn::Meta, key: &str) -> bool {
+    if let syn::Meta::NameValue(syn::MetaNameValue { path, .. }) = meta {
+        return path.is_ident(key);
+    }
+    false
+}
+
+/// The names of the fields `idents` in the documents, which are renamed by
+/// `#[entity(rename = "...")]` on the fields, or `#[entity(rename_all = "...")]` on the struct
+pub fn wire_names(ast: &syn::DeriveInput, idents: &[&syn::Ident]) -> syn::Result<Vec<String>> {
+    let rule = rename_rule(ast)?;
+    let mut names = vec![];
+    if let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(syn::FieldsNamed { ref named, .. }),
+        ..
+    }) = ast.data
+    {
+        for ident in idents {
+            let field = named
+                .iter()
+                .find(|f| f.ident.as_ref() == Some(ident))
+                .expect("the fields are collected from `ast`");
+            let name = ident.to_string().trim_start_matches("r#").to_string();
+            names.push(match (get_rename(&field.attrs, "rename")?, rule) {
+                (Some(rename), _) => rename.value(),
+                (None, Some(rule)) => rule.apply(&name),
+                (None, None) => name,
+            });
+        }
+    }
+    Ok(names)
+}
+
+/// The `names` renamed by `#[entity(rename_all = "...")]` on the struct, like the keys of
+/// `#[entity(meta(name = "method"))]`
+pub fn renamed_all(ast: &syn::DeriveInput, names: Vec<String>) -> syn::Result<Vec<String>> {
+    Ok(match rename_rule(ast)? {
+        Some(rule) => names.iter().map(|name| rule.apply(name)).collect(),
+        None => names,
+    })
+}
+
+fn rename_rule(ast: &syn::DeriveInput) -> syn::Result<Option<RenameRule>> {
+    match get_rename(&ast.attrs, "rename_all")? {
+        Some(lit_str) => Ok(Some(
+            lit_str
+                .value()
+                .parse::<RenameRule>()
+                .map_err(|_| invalid(&lit_str))?,
+        )),
+        None => Ok(None),
+    }
+}
+
+/// The PascalCase names of the fields `idents`, as the variants of the generated enums
+pub fn variant_names(idents: &[&syn::Ident]) -> Vec<syn::Ident> {
+    idents
+        .iter()
+        .map(|ident| {
+            let name = ident.to_string().trim_start_matches("r#").to_string();
+            syn::Ident::new(&RenameRule::Pascal.apply(&name), ident.span())
+        })
+        .collect()
+}
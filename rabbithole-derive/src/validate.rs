@@ -0,0 +1,195 @@
+use crate::error::EntityDecoratorError;
+use crate::field::{get_field_access, is_meta_field, is_validate, FieldAccess};
+use crate::get_decorators;
+use quote::{quote, ToTokens};
+
+/// The rules of a field in `#[entity(validate(...))]`
+#[derive(Default, PartialEq)]
+struct Rules {
+    required: bool,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    min: Option<f64>,
+    max: Option<f64>,
+    regex: Option<String>,
+}
+
+fn invalid(tokens: &impl ToTokens) -> syn::Error {
+    syn::Error::new_spanned(
+        tokens,
+        EntityDecoratorError::InvalidValidateDecorator(tokens.to_token_stream().to_string()),
+    )
+}
+
+/// Numbers can be written as strings as well, like `min = "-1"`, which is not a valid literal
+fn parse_number<N: std::str::FromStr>(lit: &syn::Lit) -> syn::Result<N>
+where
+    N::Err: std::fmt::Display,
+{
+    match lit {
+        syn::Lit::Int(int) => int.base10_parse(),
+        syn::Lit::Float(float) => float.base10_parse(),
+        syn::Lit::Str(s) => s.value().parse().map_err(|_| invalid(lit)),
+        _ => Err(invalid(lit)),
+    }
+}
+
+/// Parses `min = .., max = ..` in `length(...)` and `range(...)`
+fn parse_bounds<N: std::str::FromStr>(
+    nested: &syn::punctuated::Punctuated<syn::NestedMeta, syn::Token![,]>,
+) -> syn::Result<(Option<N>, Option<N>)>
+where
+    N::Err: std::fmt::Display,
+{
+    let (mut min, mut max) = (None, None);
+    for item in nested {
+        match item {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                path, lit, ..
+            })) if path.is_ident("min") => min = Some(parse_number(lit)?),
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                path, lit, ..
+            })) if path.is_ident("max") => max = Some(parse_number(lit)?),
+            _ => return Err(invalid(item)),
+        }
+    }
+    Ok((min, max))
+}
+
+fn get_rules(field: &syn::Field) -> syn::Result<Rules> {
+    let mut rules = Rules::default();
+    for meta in get_decorators(&field.attrs)?
+        .iter()
+        .filter(|m| is_validate(m))
+    {
+        if let syn::Meta::List(syn::MetaList { nested, .. }) = meta {
+            for item in nested {
+                match item {
+                    syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("required") => {
+                        rules.required = true
+                    },
+                    syn::NestedMeta::Meta(syn::Meta::List(syn::MetaList {
+                        path, nested, ..
+                    })) if path.is_ident("length") => {
+                        let (min, max) = parse_bounds(nested)?;
+                        rules.min_length = min;
+                        rules.max_length = max;
+                    },
+                    syn::NestedMeta::Meta(syn::Meta::List(syn::MetaList {
+                        path, nested, ..
+                    })) if path.is_ident("range") => {
+                        let (min, max) = parse_bounds(nested)?;
+                        rules.min = min;
+                        rules.max = max;
+                    },
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                        path,
+                        lit: syn::Lit::Str(lit_str),
+                        ..
+                    })) if path.is_ident("regex") => {
+                        // Compiled here as well, so an invalid pattern fails the build
+                        // rather than every request
+                        if let Err(err) = regex::Regex::new(&lit_str.value()) {
+                            return Err(syn::Error::new_spanned(
+                                lit_str,
+                                EntityDecoratorError::InvalidRegex(
+                                    lit_str.value(),
+                                    err.to_string(),
+                                ),
+                            ));
+                        }
+                        rules.regex = Some(lit_str.value())
+                    },
+                    _ => return Err(invalid(item)),
+                }
+            }
+        }
+    }
+    Ok(rules)
+}
+
+fn option_tokens<T: ToTokens>(opt: Option<T>) -> proc_macro2::TokenStream {
+    match opt {
+        Some(value) => quote!(std::option::Option::Some(#value)),
+        None => quote!(std::option::Option::None),
+    }
+}
+
+pub fn generate_validate(
+    ast: &syn::DeriveInput, id: &syn::Ident, relats: &[&syn::Ident],
+) -> syn::Result<proc_macro2::TokenStream> {
+    let decorated_struct = &ast.ident;
+    let mut checks = vec![];
+
+    if let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(syn::FieldsNamed { ref named, .. }),
+        ..
+    }) = ast.data
+    {
+        for field in named {
+            let ident = match field.ident.as_ref() {
+                Some(ident) if ident != id => ident,
+                _ => continue,
+            };
+            let access = get_field_access(field)?;
+            let rules = get_rules(field)?;
+            // The meta fields are not attributes, so the same-named attributes in the bodies are ignored
+            let read_only = access == FieldAccess::ReadOnly && !is_meta_field(field)?;
+            if access == FieldAccess::Skip || (rules == Rules::default() && !read_only) {
+                continue;
+            }
+            let Rules {
+                required,
+                min_length,
+                max_length,
+                min,
+                max,
+                regex,
+            } = rules;
+            let relationship = relats.contains(&ident);
+            let name = crate::rename::wire_names(ast, &[ident])?.remove(0);
+            let min_length = option_tokens(min_length.map(proc_macro2::Literal::usize_unsuffixed));
+            let max_length = option_tokens(max_length.map(proc_macro2::Literal::usize_unsuffixed));
+            let min = option_tokens(min.map(proc_macro2::Literal::f64_unsuffixed));
+            let max = option_tokens(max.map(proc_macro2::Literal::f64_unsuffixed));
+            let regex = option_tokens(regex.map(|pattern| {
+                quote! {{
+                    rabbithole::lazy_static! {
+                        static ref REGEX: rabbithole::regex::Regex =
+                            rabbithole::regex::Regex::new(#pattern).unwrap();
+                    }
+                    &*REGEX
+                }}
+            }));
+            checks.push(quote! {
+                rabbithole::validation::FieldRules {
+                    relationship: #relationship,
+                    required: #required,
+                    min_length: #min_length,
+                    max_length: #max_length,
+                    min: #min,
+                    max: #max,
+                    regex: #regex,
+                    read_only: #read_only,
+                }
+                .check(resource, #name, partial, &mut errors);
+            });
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics rabbithole::validation::Validate for #decorated_struct #ty_generics #where_clause {
+            #[allow(unused_mut, unused_variables)]
+            fn validate(resource: &rabbithole::model::resource::Resource, partial: bool) -> std::result::Result<(), rabbithole::model::error::Errors> {
+                let mut errors = std::vec::Vec::new();
+                #( #checks )*
+                if errors.is_empty() {
+                    Ok(())
+                } else {
+                    Err(rabbithole::model::error::Errors(errors))
+                }
+            }
+        }
+    })
+}
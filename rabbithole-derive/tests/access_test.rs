@@ -0,0 +1,206 @@
+extern crate rabbithole_derive as rbh_derive;
+extern crate serde;
+
+use rabbithole::entity::{FromResource, SingleEntity};
+use rabbithole::model::resource::Resource;
+use rabbithole::validation::Validate;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+/// Not serializable, which can only live in an entity as a skipped field
+#[derive(Debug, Default, Clone)]
+pub struct Cache(Vec<String>);
+
+/// Without `Default`, so the read-only field of it is filled by `default = "path"`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Role {
+    Guest,
+    Admin,
+}
+
+impl Role {
+    fn guest() -> Self { Role::Guest }
+}
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "users")]
+#[entity(service(UserService))]
+pub struct User {
+    #[entity(id)]
+    pub id: String,
+    pub name: String,
+    #[entity(read_only)]
+    pub created_at: u64,
+    #[entity(read_only, default = "Role::guest")]
+    pub role: Role,
+    #[entity(write_only)]
+    pub password: String,
+    #[serde(skip_deserializing)]
+    pub login_count: u32,
+    #[serde(skip)]
+    pub cache: Cache,
+    #[entity(skip)]
+    #[serde(skip)]
+    pub token: Option<String>,
+}
+
+fn user() -> User {
+    User {
+        id: "1".into(),
+        name: "Alice".into(),
+        created_at: 42,
+        role: Role::Admin,
+        password: "secret".into(),
+        login_count: 3,
+        cache: Cache(vec!["cached".into()]),
+        token: Some("token".into()),
+    }
+}
+
+#[test]
+fn rendered_fields_test() {
+    let attrs: HashSet<String> = user().attributes().keys().cloned().collect();
+    assert_eq!(
+        attrs,
+        HashSet::from_iter(vec![
+            "name".to_string(),
+            "created_at".to_string(),
+            "role".to_string(),
+            "login_count".to_string()
+        ])
+    );
+}
+
+#[test]
+fn writable_fields_test() {
+    assert!(User::is_writable("name"));
+    assert!(User::is_writable("password"));
+    assert!(!User::is_writable("created_at"));
+    assert!(!User::is_writable("role"));
+    assert!(!User::is_writable("login_count"));
+}
+
+#[test]
+fn written_fields_test() {
+    let resource: Resource = serde_json::from_value(json!({
+        "type": "users",
+        "id": "1",
+        "attributes": { "name": "Alice", "password": "secret" }
+    }))
+    .unwrap();
+    assert!(User::validate(&resource, false).is_ok());
+    let user = futures::executor::block_on(User::from_resource(&resource, &())).unwrap();
+    assert_eq!(user.password, "secret");
+    assert_eq!(user.created_at, 0);
+    assert_eq!(user.role, Role::Guest);
+    assert_eq!(user.login_count, 0);
+    assert!(user.cache.0.is_empty());
+    assert!(user.token.is_none());
+
+    let patch = futures::executor::block_on(UserPatch::from_resource(&resource, &())).unwrap();
+    assert_eq!(patch.name.as_deref(), Some("Alice"));
+    assert_eq!(patch.password.as_deref(), Some("secret"));
+
+    let resource: Resource = serde_json::from_value(json!({
+        "type": "users",
+        "id": "1",
+        "attributes": { "created_at": 1, "login_count": 1, "token": "forged" }
+    }))
+    .unwrap();
+    let errors = User::validate(&resource, true).unwrap_err();
+    let pointers: HashSet<String> = errors
+        .iter()
+        .map(|err| {
+            serde_json::to_value(&err.source).unwrap()["pointer"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        })
+        .collect();
+    assert_eq!(
+        pointers,
+        HashSet::from_iter(vec![
+            "/data/attributes/created_at".to_string(),
+            "/data/attributes/login_count".to_string()
+        ])
+    );
+}
+
+/// The access decorators listed with the relationship ones
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "teams")]
+#[entity(service(TeamService))]
+pub struct Team {
+    #[entity(id)]
+    pub id: String,
+    #[entity(to_many(type = "users"), read_only)]
+    pub members: Vec<String>,
+    #[entity(read_only, to_one(type = "users"))]
+    pub owner: Option<String>,
+}
+
+#[test]
+fn read_only_relationship_test() {
+    let team = Team {
+        id: "1".into(),
+        members: vec!["1".into(), "2".into()],
+        owner: Some("1".into()),
+    };
+    assert!(team.attributes().is_empty());
+    let relats: HashSet<String> = team.relationships("").keys().cloned().collect();
+    assert_eq!(
+        relats,
+        HashSet::from_iter(vec!["members".to_string(), "owner".to_string()])
+    );
+    assert!(!Team::is_writable("members"));
+    assert!(!Team::is_writable("owner"));
+
+    let resource: Resource = serde_json::from_value(json!({
+        "type": "teams",
+        "id": "1",
+        "relationships": {
+            "members": { "data": [{ "type": "users", "id": "3" }] },
+            "owner": { "data": { "type": "users", "id": "3" } }
+        }
+    }))
+    .unwrap();
+    let errors = Team::validate(&resource, true).unwrap_err();
+    let pointers: HashSet<String> = errors
+        .iter()
+        .map(|err| {
+            serde_json::to_value(&err.source).unwrap()["pointer"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        })
+        .collect();
+    assert_eq!(
+        pointers,
+        HashSet::from_iter(vec![
+            "/data/relationships/members".to_string(),
+            "/data/relationships/owner".to_string()
+        ])
+    );
+}
+
+/// The json keys must be strings, so the `cells` cannot be serialized into json
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "grids")]
+#[entity(service(GridService))]
+pub struct Grid {
+    #[entity(id)]
+    pub id: String,
+    pub cells: std::collections::HashMap<(u32, u32), u32>,
+}
+
+#[test]
+#[should_panic(expected = "the field `cells` of the entity `grids` cannot be serialized")]
+fn unserializable_field_test() {
+    let grid = Grid {
+        id: "1".into(),
+        cells: std::iter::once(((0, 0), 1)).collect(),
+    };
+    grid.attributes();
+}
@@ -153,10 +153,10 @@ fn general_test() {
         )])
         .into(),
         relationships: HashMap::from_iter(vec![("only_flea".into(), Relationship {
-            data: IdentifierData::Single(Some(ResourceIdentifier {
+            data: Some(IdentifierData::Single(Some(ResourceIdentifier {
                 ty: "fleas".to_string(),
                 id: "1".to_string(),
-            })),
+            }))),
             links: HashMap::from_iter(vec![
                 (
                     "self".into(),
@@ -239,7 +239,7 @@ fn general_test() {
         .into(),
         relationships: HashMap::from_iter(vec![
             ("friends".into(), Relationship {
-                data: IdentifierData::Multiple(Default::default()),
+                data: Some(IdentifierData::Multiple(Default::default())),
                 links: HashMap::from_iter(vec![
                     (
                         "self".into(),
@@ -258,7 +258,7 @@ fn general_test() {
                 meta: Default::default(),
             }),
             ("fleas".into(), Relationship {
-                data: IdentifierData::Multiple(vec![
+                data: Some(IdentifierData::Multiple(vec![
                     ResourceIdentifier {
                         ty: "fleas".to_string(),
                         id: "a".to_string(),
@@ -267,7 +267,7 @@ fn general_test() {
                         ty: "fleas".to_string(),
                         id: "b".to_string(),
                     },
-                ]),
+                ])),
                 links: HashMap::from_iter(vec![
                     (
                         "self".into(),
@@ -286,10 +286,10 @@ fn general_test() {
                 ..Default::default()
             }),
             ("master".into(), Relationship {
-                data: IdentifierData::Single(Some(ResourceIdentifier {
+                data: Some(IdentifierData::Single(Some(ResourceIdentifier {
                     ty: "humans".to_string(),
                     id: "number".to_string(),
-                })),
+                }))),
                 links: HashMap::from_iter(vec![
                     (
                         "self".into(),
@@ -307,6 +307,25 @@ fn general_test() {
                 .into(),
                 ..Default::default()
             }),
+            ("best_one".into(), Relationship {
+                data: Some(IdentifierData::Single(None)),
+                links: HashMap::from_iter(vec![
+                    (
+                        "self".into(),
+                        "https://example.com/api/dogs/1/relationships/best_one"
+                            .parse::<Link>()
+                            .unwrap(),
+                    ),
+                    (
+                        "related".into(),
+                        "https://example.com/api/dogs/1/best_one"
+                            .parse::<Link>()
+                            .unwrap(),
+                    ),
+                ])
+                .into(),
+                ..Default::default()
+            }),
         ]),
         links: HashMap::from_iter(vec![(
             "self".into(),
@@ -0,0 +1,124 @@
+extern crate rabbithole_derive as rbh_derive;
+extern crate serde;
+
+use rabbithole::entity::{Entity, FromResource, SingleEntity};
+use rabbithole::model::resource::{IdentifierData, Resource, ResourceIdentifier};
+use rabbithole::validation::Validate;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "dogs")]
+#[entity(service(DogService))]
+pub struct Dog {
+    #[entity(id)]
+    pub id: Uuid,
+    pub name: String,
+    #[entity(to_one(type = "people"))]
+    #[entity(rename = "master")]
+    pub master_id: Uuid,
+    #[entity(to_one(type = "fleas"))]
+    pub flea: Option<u64>,
+    #[entity(to_many(type = "dogs"))]
+    #[entity(validate(length(max = 2)))]
+    pub friends: Vec<Uuid>,
+}
+
+fn dog() -> Dog {
+    Dog {
+        id: Uuid::new_v4(),
+        name: "Fido".into(),
+        master_id: Uuid::nil(),
+        flea: None,
+        friends: vec![Uuid::nil()],
+    }
+}
+
+#[test]
+fn lazy_relationships_test() {
+    let dog = dog();
+    assert_eq!(dog.attributes().keys().collect::<Vec<_>>(), vec!["name"]);
+
+    let relats = dog.relationships("https://example.com/api");
+    assert_eq!(
+        relats.get("master").unwrap().data,
+        Some(IdentifierData::Single(Some(ResourceIdentifier {
+            ty: "people".into(),
+            id: Uuid::nil().to_string(),
+        })))
+    );
+    assert_eq!(
+        relats.get("flea").unwrap().data,
+        Some(IdentifierData::Single(None))
+    );
+    assert_eq!(
+        relats.get("friends").unwrap().data,
+        Some(IdentifierData::Multiple(vec![ResourceIdentifier {
+            ty: "dogs".into(),
+            id: Uuid::nil().to_string(),
+        }]))
+    );
+    assert!(dog
+        .included(
+            "https://example.com/api",
+            &None,
+            &Default::default(),
+            &rabbithole::policy::AllowAll
+        )
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn lazy_body_test() {
+    let master = Uuid::new_v4();
+    let resource: Resource = serde_json::from_value(json!({
+        "type": "dogs",
+        "id": Uuid::nil().to_string(),
+        "attributes": { "name": "Fido" },
+        "relationships": {
+            "master": { "data": { "type": "people", "id": master.to_string() } },
+            "flea": { "data": { "type": "fleas", "id": "42" } }
+        }
+    }))
+    .unwrap();
+    let dog = futures::executor::block_on(Dog::from_resource(&resource, &())).unwrap();
+    assert_eq!(dog.master_id, master);
+    assert_eq!(dog.flea, Some(42));
+    assert!(dog.friends.is_empty());
+
+    let patch = futures::executor::block_on(DogPatch::from_resource(&resource, &())).unwrap();
+    assert_eq!(patch.master_id, Some(master));
+    assert_eq!(patch.flea, Some(Some(42)));
+    assert_eq!(patch.friends, None);
+
+    let resource: Resource = serde_json::from_value(json!({
+        "type": "dogs",
+        "id": Uuid::nil().to_string(),
+        "attributes": { "name": "Fido" },
+        "relationships": {
+            "master": { "data": { "type": "dogs", "id": master.to_string() } },
+            "friends": { "data": [
+                { "type": "dogs", "id": "1" },
+                { "type": "dogs", "id": "2" },
+                { "type": "dogs", "id": "3" }
+            ] }
+        }
+    }))
+    .unwrap();
+    let err = futures::executor::block_on(Dog::from_resource(&resource, &()))
+        .err()
+        .unwrap();
+    assert_eq!(err.code.as_deref(), Some("RBH-0203"));
+    assert_eq!(err.status.as_deref(), Some("409"));
+    assert_eq!(
+        serde_json::to_value(&err.source).unwrap()["pointer"],
+        "/data/relationships/master"
+    );
+    let errors = Dog::validate(&resource, false).unwrap_err();
+    assert_eq!(
+        serde_json::to_value(&errors.0[0].source).unwrap()["pointer"],
+        "/data/relationships/friends"
+    );
+}
@@ -0,0 +1,193 @@
+extern crate rabbithole_derive as rbh_derive;
+extern crate serde;
+
+use rabbithole::entity::{Entity, SingleEntity};
+use rabbithole::model::document::{DocumentItem, PrimaryDataItem};
+use rabbithole::model::relationship::Linkage;
+use rabbithole::model::resource::{IdentifierData, Resource};
+use rabbithole::operation::{Fetching, Operation, OperationResultData, SingleResult};
+use rabbithole::query::{FieldsQuery, Query};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "photos")]
+#[entity(service(PhotoService))]
+pub struct Photo {
+    #[entity(id)]
+    pub id: String,
+}
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "albums")]
+#[entity(service(AlbumService))]
+pub struct Album {
+    #[entity(id)]
+    pub id: String,
+    #[entity(to_one)]
+    pub cover: Option<Photo>,
+    #[entity(to_many(linkage = "never"))]
+    #[entity(count)]
+    pub photos: Vec<Photo>,
+    #[entity(to_many(type = "people", linkage = "when_included"))]
+    pub viewers: Vec<String>,
+    #[entity(to_one(type = "people", linkage = "always"))]
+    pub owner: Option<String>,
+}
+
+pub struct AlbumService;
+
+impl Operation for AlbumService {
+    type Item = Album;
+}
+
+#[rabbithole::async_trait]
+impl Fetching for AlbumService {
+    async fn fetch_single(
+        &self, _id: &String, _uri: &str, _path: &http::Uri, _query: &Query,
+    ) -> SingleResult<Album> {
+        Ok(OperationResultData {
+            data: Some(album()),
+            ..Default::default()
+        })
+    }
+}
+
+fn album() -> Album {
+    Album {
+        id: "1".into(),
+        cover: None,
+        photos: vec![Photo { id: "1".into() }, Photo { id: "2".into() }],
+        viewers: vec!["1".into()],
+        owner: None,
+    }
+}
+
+fn render(query: &Query) -> Resource {
+    let doc = SingleEntity::to_document(
+        &album(),
+        "https://example.com/api",
+        query,
+        "https://example.com/api/albums/1".parse().unwrap(),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    match doc.item {
+        DocumentItem::PrimaryData(Some((PrimaryDataItem::Single(resource), included))) => {
+            assert!(included.is_empty());
+            *resource
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn linkage_test() {
+    let album = album();
+    assert_eq!(album.linkage("photos"), Linkage::Never);
+    assert_eq!(album.linkage("viewers"), Linkage::WhenIncluded);
+    assert_eq!(album.linkage("owner"), Linkage::Always);
+
+    // Without the `include` query, nothing is included, just like the empty one
+    for query in &[Query::default(), Query {
+        include: Some(Default::default()),
+        ..Default::default()
+    }] {
+        let resource = render(query);
+        let relats = serde_json::to_value(&resource.relationships).unwrap();
+        assert_eq!(relats["cover"]["data"], json!(null));
+        assert_eq!(relats["owner"]["data"], json!(null));
+        assert!(relats["photos"].get("data").is_none());
+        assert_eq!(relats["photos"]["meta"]["count"], json!(2));
+        assert!(relats["photos"]["links"].get("related").is_some());
+        assert!(relats["viewers"].get("data").is_none());
+    }
+
+    let resource = render(&Query {
+        include: Some(HashSet::from_iter(vec!["viewers".to_string()])),
+        ..Default::default()
+    });
+    assert_eq!(
+        resource.relationships["viewers"].data,
+        Some(IdentifierData::Multiple(vec![
+            rabbithole::model::resource::ResourceIdentifier::new("people", "1")
+        ]))
+    );
+    assert!(resource.relationships["photos"].data.is_none());
+
+    // The relationships never linked cannot be included
+    let err = Entity::included(
+        &album,
+        "https://example.com/api",
+        &Some(HashSet::from_iter(vec!["photos".to_string()])),
+        &Default::default(),
+        &rabbithole::policy::AllowAll,
+    )
+    .unwrap_err();
+    assert_eq!(err.status.as_deref(), Some("400"));
+    assert_eq!(err.code.as_deref(), Some("RBH-0004"));
+    assert_eq!(
+        serde_json::to_value(&err.source).unwrap()["parameter"],
+        json!("include")
+    );
+}
+
+#[test]
+fn null_linkage_body_test() {
+    let resource: Resource = serde_json::from_value(json!({
+        "type": "albums",
+        "id": "1",
+        "relationships": {
+            "cover": { "data": null },
+            "photos": { "links": { "related": "https://example.com/api/albums/1/photos" } }
+        }
+    }))
+    .unwrap();
+    assert_eq!(
+        resource.relationships["cover"].data,
+        Some(IdentifierData::Single(None))
+    );
+    assert!(resource.relationships["photos"].data.is_none());
+}
+
+#[test]
+fn relationship_route_linkage_test() {
+    let fetch = |field: &str, query: &Query| {
+        let path = format!("https://example.com/api/albums/1/relationships/{}", field);
+        futures::executor::block_on(AlbumService.fetch_relationship(
+            &"1".to_string(),
+            field,
+            "https://example.com/api",
+            &path.parse().unwrap(),
+            query,
+        ))
+        .map(|relat| relat.data.data)
+    };
+
+    // The data of the relationship route is responded whatever the linkage is
+    match fetch("photos", &Default::default()).unwrap() {
+        Some(IdentifierData::Multiple(photos)) => assert_eq!(photos.len(), 2),
+        _ => unreachable!(),
+    }
+
+    // And the sparse fieldsets of the parent are for the resource routes only
+    let mut fields = FieldsQuery::new();
+    fields.insert(
+        "albums".into(),
+        HashSet::from_iter(vec!["cover".to_string()]),
+    );
+    let query = Query {
+        fields,
+        ..Default::default()
+    };
+    match fetch("viewers", &query).unwrap() {
+        Some(IdentifierData::Multiple(viewers)) => assert_eq!(viewers.len(), 1),
+        _ => unreachable!(),
+    }
+
+    let err = fetch("id", &query).unwrap_err();
+    assert_eq!(err.0[0].code.as_deref(), Some("RBH-0401"));
+}
@@ -0,0 +1,175 @@
+extern crate rabbithole_derive as rbh_derive;
+extern crate serde;
+
+use rabbithole::entity::{FromResource, SingleEntity};
+use rabbithole::model::link::Link;
+use rabbithole::model::resource::Resource;
+use rabbithole::policy::FieldVisibility;
+use rabbithole::validation::Validate;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::iter::FromIterator;
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "comments")]
+#[entity(service(CommentService))]
+pub struct Comment {
+    #[entity(id)]
+    pub id: String,
+    pub body: String,
+    #[entity(meta)]
+    pub edited: bool,
+}
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "articles")]
+#[entity(service(ArticleService))]
+#[entity(rename_all = "camelCase")]
+#[entity(meta(word_count = "word_count"))]
+#[entity(links(comments = "comments_link", author = "author_link"))]
+pub struct Article {
+    #[entity(id)]
+    pub id: String,
+    pub title: String,
+    #[entity(meta)]
+    #[entity(rename = "updatedAt")]
+    pub updated_at: u64,
+    #[entity(to_many)]
+    #[entity(count)]
+    pub comments: Vec<Comment>,
+    #[entity(to_many(type = "tags"))]
+    #[entity(count)]
+    pub tags: Vec<String>,
+    #[entity(to_many(type = "people"))]
+    pub readers: Vec<String>,
+}
+
+impl Article {
+    fn word_count(&self) -> usize { self.title.split_whitespace().count() }
+
+    fn comments_link(&self, uri: &str) -> Link {
+        format!("{}/comments?filter[article]={}", uri, self.id)
+            .parse()
+            .unwrap()
+    }
+
+    fn author_link(&self, _uri: &str) -> http::Uri {
+        "https://example.com/authors/1".parse().unwrap()
+    }
+}
+
+fn article() -> Article {
+    Article {
+        id: "1".into(),
+        title: "Hello Rabbit Hole".into(),
+        updated_at: 42,
+        comments: vec![Comment {
+            id: "1".into(),
+            body: "First".into(),
+            edited: true,
+        }],
+        tags: vec!["rust".into(), "api".into()],
+        readers: vec!["1".into()],
+    }
+}
+
+#[test]
+fn resource_meta_test() {
+    let article = article();
+    assert_eq!(article.attributes().keys().collect::<Vec<_>>(), vec![
+        "title"
+    ]);
+    let resource = article
+        .to_resource(
+            "https://example.com/api",
+            &Default::default(),
+            &rabbithole::policy::AllowAll,
+        )
+        .unwrap();
+    assert_eq!(resource.meta.get("updatedAt"), Some(&json!(42)));
+    assert_eq!(resource.meta.get("wordCount"), Some(&json!(3)));
+    assert_eq!(resource.links.len(), 3);
+    assert_eq!(
+        resource.links.get("self"),
+        Some(&"https://example.com/api/articles/1".parse().unwrap())
+    );
+    assert_eq!(
+        resource.links.get("comments"),
+        Some(
+            &"https://example.com/api/comments?filter[article]=1"
+                .parse()
+                .unwrap()
+        )
+    );
+    assert_eq!(
+        resource.links.get("author"),
+        Some(&"https://example.com/authors/1".parse().unwrap())
+    );
+}
+
+/// Hides the `updatedAt` of the articles
+struct NoUpdates;
+
+impl FieldVisibility for NoUpdates {
+    fn can_read_field(&self, ty: &str, field: &str) -> bool {
+        !(ty == "articles" && field == "updatedAt")
+    }
+}
+
+#[test]
+fn hidden_meta_test() {
+    let article = article();
+    let resource = article
+        .to_resource("https://example.com/api", &Default::default(), &NoUpdates)
+        .unwrap();
+    assert_eq!(resource.meta.keys().collect::<Vec<_>>(), vec!["wordCount"]);
+
+    let fields = HashMap::from_iter(vec![(
+        "articles".to_string(),
+        HashSet::from_iter(vec!["title".to_string(), "updatedAt".to_string()]),
+    )]);
+    let resource = article
+        .to_resource(
+            "https://example.com/api",
+            &fields,
+            &rabbithole::policy::AllowAll,
+        )
+        .unwrap();
+    assert_eq!(resource.meta.keys().collect::<Vec<_>>(), vec!["updatedAt"]);
+}
+
+#[test]
+fn relationship_meta_test() {
+    let relats = article().relationships("https://example.com/api");
+    assert_eq!(
+        relats.get("comments").unwrap().meta.get("count"),
+        Some(&json!(1))
+    );
+    assert_eq!(
+        relats.get("tags").unwrap().meta.get("count"),
+        Some(&json!(2))
+    );
+    assert!(relats.get("readers").unwrap().meta.is_empty());
+}
+
+#[test]
+fn meta_body_test() {
+    let resource: Resource = serde_json::from_value(json!({
+        "type": "articles",
+        "id": "1",
+        "attributes": { "title": "Hello", "updatedAt": 1 }
+    }))
+    .unwrap();
+    assert!(Article::validate(&resource, false).is_ok());
+
+    let resource: Resource = serde_json::from_value(json!({
+        "type": "comments",
+        "id": "1",
+        "attributes": { "body": "First", "edited": true }
+    }))
+    .unwrap();
+    let comment = futures::executor::block_on(Comment::from_resource(&resource, &())).unwrap();
+    assert!(!comment.edited);
+    assert!(comment.attributes().get_field("edited").is_err());
+}
@@ -0,0 +1,171 @@
+extern crate rabbithole_derive as rbh_derive;
+extern crate serde;
+
+use rabbithole::entity::{Entity, FromResource, Patchable, SingleEntity};
+use rabbithole::model::resource::{IdentifierData, Resource, ResourceIdentifier};
+use rabbithole::validation::Validate;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "images")]
+#[entity(service(ImageService))]
+pub struct Image {
+    #[entity(id)]
+    pub id: String,
+    pub width: u32,
+}
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "videos")]
+#[entity(service(VideoService))]
+pub struct Video {
+    #[entity(id)]
+    pub id: String,
+    #[entity(validate(range(max = 3600)))]
+    pub duration: u32,
+}
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "attachments")]
+pub enum Attachment {
+    Image(Image),
+    Video(Video),
+}
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+pub struct Cover(Image);
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "posts")]
+#[entity(service(PostService))]
+pub struct Post {
+    #[entity(id)]
+    pub id: String,
+    #[entity(to_one)]
+    pub cover: Cover,
+    #[entity(to_many)]
+    pub attachments: Vec<Attachment>,
+}
+
+fn post() -> Post {
+    Post {
+        id: "1".into(),
+        cover: Cover(Image {
+            id: "0".into(),
+            width: 640,
+        }),
+        attachments: vec![
+            Attachment::Image(Image {
+                id: "1".into(),
+                width: 800,
+            }),
+            Attachment::Video(Video {
+                id: "1".into(),
+                duration: 60,
+            }),
+        ],
+    }
+}
+
+fn identifier(ty: &str, id: &str) -> ResourceIdentifier {
+    ResourceIdentifier {
+        ty: ty.into(),
+        id: id.into(),
+    }
+}
+
+#[test]
+fn mixed_relationship_test() {
+    let post = post();
+    let relats = post.relationships("https://example.com/api");
+    assert_eq!(
+        relats.get("cover").unwrap().data,
+        Some(IdentifierData::Single(Some(identifier("images", "0"))))
+    );
+    assert_eq!(
+        relats.get("attachments").unwrap().data,
+        Some(IdentifierData::Multiple(vec![
+            identifier("images", "1"),
+            identifier("videos", "1")
+        ]))
+    );
+
+    let included = post
+        .included(
+            "https://example.com/api",
+            &None,
+            &Default::default(),
+            &rabbithole::policy::AllowAll,
+        )
+        .unwrap();
+    assert_eq!(included.len(), 3);
+    let video = included.get(&identifier("videos", "1")).unwrap();
+    assert_eq!(
+        video.links.get("self"),
+        Some(&"https://example.com/api/videos/1".parse().unwrap())
+    );
+    assert_eq!(video.attributes.get_field("duration").unwrap().0, json!(60));
+}
+
+#[test]
+fn delegated_entity_test() {
+    assert_eq!(Attachment::ty(), "attachments");
+    assert_eq!(Cover::ty(), "images");
+    // The bodies posted to the routes of an enum have the types of its variants
+    assert!(Attachment::accepts_type("images"));
+    assert!(Attachment::accepts_type("videos"));
+    assert!(!Attachment::accepts_type("attachments"));
+    assert!(Cover::accepts_type("images"));
+    assert!(!Image::accepts_type("videos"));
+    let cover = Cover(Image {
+        id: "0".into(),
+        width: 640,
+    });
+    assert_eq!(cover.id(), "0");
+    let mut cover = cover;
+    cover.apply_patch(ImagePatch { width: Some(1024) });
+    assert_eq!(cover.0.width, 1024);
+}
+
+#[test]
+fn polymorphic_body_test() {
+    let resource: Resource = serde_json::from_value(json!({
+        "type": "videos",
+        "id": "2",
+        "attributes": { "duration": 30 }
+    }))
+    .unwrap();
+    assert!(Attachment::validate(&resource, false).is_ok());
+    let attachment =
+        futures::executor::block_on(Attachment::from_resource(&resource, &())).unwrap();
+    assert!(matches!(
+        attachment,
+        Attachment::Video(Video { duration: 30, .. })
+    ));
+
+    let resource: Resource = serde_json::from_value(json!({
+        "type": "videos",
+        "id": "2",
+        "attributes": { "duration": 7200 }
+    }))
+    .unwrap();
+    assert!(Attachment::validate(&resource, false).is_err());
+
+    let resource: Resource = serde_json::from_value(json!({
+        "type": "audios",
+        "id": "2",
+        "attributes": { "duration": 30 }
+    }))
+    .unwrap();
+    let err = futures::executor::block_on(Attachment::from_resource(&resource, &()))
+        .err()
+        .unwrap();
+    assert_eq!(err.status.as_deref(), Some("409"));
+    assert_eq!(
+        serde_json::to_value(&err.source).unwrap()["pointer"],
+        "/data/type"
+    );
+    let errors = Attachment::validate(&resource, false).unwrap_err();
+    assert_eq!(errors.0.len(), 1);
+}
@@ -5,6 +5,7 @@ use rabbithole::entity::{Entity, SingleEntity};
 use rabbithole::model::document::{Document, Included};
 use rabbithole::model::link::Link;
 use rabbithole::model::resource::Resource;
+use rabbithole::policy::AllowAll;
 use rabbithole::query::Query;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -80,14 +81,14 @@ fn default_include_test() {
 
     let master_reses: Vec<Resource> = master_vec
         .iter()
-        .map(|h| h.to_resource(uri, &Default::default()).unwrap())
+        .map(|h| h.to_resource(uri, &Default::default(), &AllowAll).unwrap())
         .collect();
 
     let mut manual_included: Included = Default::default();
     for m in master_vec {
         for d in m.dogs {
             let d_res: Resource = d
-                .to_resource(uri, &Default::default())
+                .to_resource(uri, &Default::default(), &AllowAll)
                 .unwrap()
                 .try_into()
                 .unwrap();
@@ -120,7 +121,7 @@ fn only_unknown_include_test() {
 
     let master_reses: Vec<Resource> = master_vec
         .iter()
-        .map(|h| h.to_resource(uri, &Default::default()).unwrap())
+        .map(|h| h.to_resource(uri, &Default::default(), &AllowAll).unwrap())
         .collect();
     let mut manual_doc = Document::multiple_resources(master_reses, Default::default());
     manual_doc.extend_links(HashMap::from_iter(vec![Link::slf(
@@ -148,7 +149,7 @@ fn not_included_fields_but_retain_attributes() {
 
     let master_reses: Vec<Resource> = master_vec
         .iter()
-        .map(|h| h.to_resource(uri, &Default::default()).unwrap())
+        .map(|h| h.to_resource(uri, &Default::default(), &AllowAll).unwrap())
         .collect();
     let mut manual_doc = Document::multiple_resources(master_reses, Default::default());
     manual_doc.extend_links(HashMap::from_iter(vec![Link::slf(
@@ -180,13 +181,13 @@ fn not_foreign_attributes_but_retain_included_fields() {
 
     let master_reses: Vec<Resource> = master_vec
         .iter()
-        .map(|h| h.to_resource(uri, &fields_query).unwrap())
+        .map(|h| h.to_resource(uri, &fields_query, &AllowAll).unwrap())
         .collect();
     let mut manual_included: Included = Default::default();
     for m in master_vec {
         for d in m.dogs {
             let d_res: Resource = d
-                .to_resource(uri, &Default::default())
+                .to_resource(uri, &Default::default(), &AllowAll)
                 .unwrap()
                 .try_into()
                 .unwrap();
@@ -0,0 +1,100 @@
+extern crate rabbithole_derive as rbh_derive;
+extern crate serde;
+
+use rabbithole::entity::SingleEntity;
+use rabbithole::model::document::{Document, DocumentItem, PrimaryDataItem};
+use rabbithole::query::Query;
+use serde::{Deserialize, Serialize};
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "tracks")]
+#[entity(service(TrackService))]
+pub struct Track {
+    #[entity(id)]
+    pub id: String,
+    pub title: String,
+}
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "playlists")]
+#[entity(service(PlaylistService))]
+#[entity(rename_all = "camelCase")]
+pub struct Playlist {
+    #[entity(id)]
+    pub id: String,
+    #[entity(to_one)]
+    pub first_track: Option<Track>,
+    #[entity(to_many)]
+    pub tracks: Vec<Track>,
+    #[entity(to_many(type = "people"))]
+    pub followers: Vec<String>,
+}
+
+fn playlist() -> Playlist {
+    Playlist {
+        id: "1".into(),
+        first_track: None,
+        tracks: vec![
+            Track {
+                id: "1".into(),
+                title: "Intro".into(),
+            },
+            Track {
+                id: "2".into(),
+                title: "Outro".into(),
+            },
+        ],
+        followers: vec!["1".into()],
+    }
+}
+
+fn related(field: &str) -> rabbithole::Result<Document> {
+    playlist().related_document(
+        field,
+        "https://example.com/api",
+        &Query::default(),
+        format!("https://example.com/api/playlists/1/{}", field)
+            .parse()
+            .unwrap(),
+    )
+}
+
+#[test]
+fn relationship_enum_test() {
+    assert_eq!(
+        "firstTrack".parse::<PlaylistRelationship>().unwrap(),
+        PlaylistRelationship::FirstTrack
+    );
+    assert_eq!(
+        "tracks".parse::<PlaylistRelationship>().unwrap(),
+        PlaylistRelationship::Tracks
+    );
+    assert_eq!(PlaylistRelationship::FirstTrack.to_string(), "firstTrack");
+    assert_eq!(PlaylistRelationship::Followers.to_string(), "followers");
+    let err = "first_track".parse::<PlaylistRelationship>().unwrap_err();
+    assert_eq!(err.code.as_deref(), Some("RBH-0401"));
+}
+
+#[test]
+fn related_document_test() {
+    match related("tracks").unwrap().item {
+        DocumentItem::PrimaryData(Some((PrimaryDataItem::Multiple(resources), _))) => {
+            assert_eq!(resources.len(), 2);
+            assert_eq!(resources[1].id.ty, "tracks");
+            assert_eq!(resources[1].id.id, "2");
+        },
+        _ => unreachable!(),
+    }
+    assert_eq!(
+        related("firstTrack").unwrap().item,
+        DocumentItem::PrimaryData(None)
+    );
+    assert_eq!(
+        related("followers").unwrap_err().code.as_deref(),
+        Some("RBH-9905")
+    );
+    assert_eq!(
+        related("title").unwrap_err().code.as_deref(),
+        Some("RBH-0401")
+    );
+}
@@ -0,0 +1,118 @@
+extern crate rabbithole_derive as rbh_derive;
+extern crate serde;
+
+use rabbithole::entity::{EntityLookup, FromResource, SingleEntity};
+use rabbithole::model::resource::{Resource, ResourceIdentifier};
+use rabbithole::query::sort::{OrderType, SortQuery};
+use rabbithole::validation::Validate;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::iter::FromIterator;
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "owners")]
+#[entity(service(OwnerService))]
+#[entity(rename_all = "camelCase")]
+pub struct Owner {
+    #[entity(id)]
+    pub id: String,
+    pub first_name: String,
+    #[entity(rename = "passport")]
+    #[entity(validate(required))]
+    pub passport_number: Option<String>,
+    #[entity(to_many)]
+    pub best_friends: Vec<Owner>,
+}
+
+struct Owners;
+
+#[rabbithole::async_trait]
+impl EntityLookup<Owner> for Owners {
+    async fn lookup_entities(&self, ids: &[ResourceIdentifier]) -> rabbithole::Result<Vec<Owner>> {
+        Ok(ids.iter().map(|id| owner(&id.id, "friend")).collect())
+    }
+}
+
+fn owner(id: &str, first_name: &str) -> Owner {
+    Owner {
+        id: id.into(),
+        first_name: first_name.into(),
+        passport_number: Some(format!("P-{}", id)),
+        best_friends: vec![],
+    }
+}
+
+#[test]
+fn renamed_fields_test() {
+    let mut alice = owner("1", "Alice");
+    alice.best_friends.push(owner("2", "Bob"));
+
+    let attrs: HashSet<String> = alice.attributes().keys().cloned().collect();
+    assert_eq!(
+        attrs,
+        HashSet::from_iter(vec!["firstName".to_string(), "passport".to_string()])
+    );
+    let relats = alice.relationships("https://example.com/api");
+    let best_friends = relats.get("bestFriends").unwrap();
+    assert_eq!(
+        serde_json::to_value(&best_friends.links).unwrap()["self"],
+        "https://example.com/api/owners/1/relationships/bestFriends"
+    );
+
+    let fields: HashMap<String, HashSet<String>> = HashMap::from_iter(vec![(
+        "owners".to_string(),
+        HashSet::from_iter(vec!["firstName".to_string()]),
+    )]);
+    let resource = alice
+        .to_resource(
+            "https://example.com/api",
+            &fields,
+            &rabbithole::policy::AllowAll,
+        )
+        .unwrap();
+    assert_eq!(resource.attributes.keys().collect::<Vec<_>>(), vec![
+        "firstName"
+    ]);
+    assert!(resource.relationships.is_empty());
+
+    let mut owners = vec![owner("1", "Bob"), owner("2", "Alice")];
+    SortQuery::try_from(vec![("firstName".to_string(), OrderType::Asc)])
+        .unwrap()
+        .sort(&mut owners)
+        .unwrap();
+    assert_eq!(owners[0].first_name, "Alice");
+}
+
+#[test]
+fn renamed_body_test() {
+    let resource: Resource = serde_json::from_value(json!({
+        "type": "owners",
+        "id": "1",
+        "attributes": { "firstName": "Alice", "passport": "P-1" },
+        "relationships": {
+            "bestFriends": { "data": [{ "type": "owners", "id": "2" }] }
+        }
+    }))
+    .unwrap();
+    let alice = futures::executor::block_on(Owner::from_resource(&resource, &Owners)).unwrap();
+    assert_eq!(alice.first_name, "Alice");
+    assert_eq!(alice.passport_number.as_deref(), Some("P-1"));
+    assert_eq!(alice.best_friends[0].id, "2");
+
+    let patch = futures::executor::block_on(OwnerPatch::from_resource(&resource, &Owners)).unwrap();
+    assert_eq!(patch.first_name.as_deref(), Some("Alice"));
+
+    let resource: Resource = serde_json::from_value(json!({
+        "type": "owners",
+        "id": "1",
+        "attributes": { "firstName": "Alice" }
+    }))
+    .unwrap();
+    let err = Owner::validate(&resource, false).unwrap_err();
+    assert_eq!(
+        serde_json::to_value(&err.0[0].source).unwrap()["pointer"],
+        "/data/attributes/passport"
+    );
+}
@@ -0,0 +1,97 @@
+extern crate rabbithole_derive as rbh_derive;
+extern crate serde;
+
+use rabbithole::model::resource::Resource;
+use rabbithole::validation::Validate;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "cats")]
+#[entity(service(CatService))]
+pub struct Cat {
+    #[entity(id)]
+    pub id: String,
+    #[entity(validate(required, length(min = 1, max = 8), regex = "^[a-z]+$"))]
+    pub name: String,
+    #[entity(validate(range(min = "-1", max = 20.5)))]
+    pub age: Option<f64>,
+    #[entity(to_many)]
+    #[entity(validate(length(max = 1)))]
+    pub kittens: Vec<Cat>,
+    #[entity(validate(required))]
+    #[entity(to_one)]
+    pub mother: Option<Box<Cat>>,
+}
+
+fn resource(value: serde_json::Value) -> Resource { serde_json::from_value(value).unwrap() }
+
+fn pointers(res: Result<(), rabbithole::model::error::Errors>) -> Vec<(String, String)> {
+    res.unwrap_err()
+        .into_iter()
+        .map(|err| {
+            (
+                err.code.unwrap(),
+                serde_json::to_value(&err.source).unwrap()["pointer"]
+                    .as_str()
+                    .unwrap()
+                    .to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn valid_test() {
+    let cat = resource(json!({
+        "type": "cats",
+        "id": "1",
+        "attributes": { "name": "tom", "age": -1 },
+        "relationships": {
+            "kittens": { "data": [{ "type": "cats", "id": "2" }] },
+            "mother": { "data": { "type": "cats", "id": "3" } }
+        }
+    }));
+    assert!(Cat::validate(&cat, false).is_ok());
+}
+
+#[test]
+fn invalid_test() {
+    let cat = resource(json!({
+        "type": "cats",
+        "id": "1",
+        "attributes": { "name": "Tom the cat", "age": 21 },
+        "relationships": {
+            "kittens": { "data": [{ "type": "cats", "id": "2" }, { "type": "cats", "id": "3" }] },
+            "mother": { "data": null }
+        }
+    }));
+    let errors = pointers(Cat::validate(&cat, false));
+    assert_eq!(errors, vec![
+        ("RBH-0211".into(), "/data/attributes/name".into()),
+        ("RBH-0213".into(), "/data/attributes/name".into()),
+        ("RBH-0212".into(), "/data/attributes/age".into()),
+        ("RBH-0211".into(), "/data/relationships/kittens".into()),
+        ("RBH-0210".into(), "/data/relationships/mother".into()),
+    ]);
+}
+
+#[test]
+fn partial_test() {
+    let cat = resource(json!({ "type": "cats", "id": "1", "attributes": { "age": 1 } }));
+    assert!(Cat::validate(&cat, true).is_ok());
+
+    let errors = pointers(Cat::validate(&cat, false));
+    assert_eq!(errors, vec![
+        ("RBH-0210".into(), "/data/attributes/name".into()),
+        ("RBH-0210".into(), "/data/relationships/mother".into()),
+    ]);
+
+    // The present fields are still checked
+    let cat = resource(json!({ "type": "cats", "id": "1", "attributes": { "name": null } }));
+    let errors = pointers(Cat::validate(&cat, true));
+    assert_eq!(errors, vec![(
+        "RBH-0210".into(),
+        "/data/attributes/name".into()
+    )]);
+}
@@ -1,14 +1,25 @@
 pub mod middleware;
 pub mod settings;
 
+use actix_web::error::JsonPayloadError;
 use actix_web::web;
 use actix_web::{HttpRequest, HttpResponse};
 use futures::lock::Mutex;
-use rabbithole::entity::{Entity, SingleEntity};
+use rabbithole::entity::{Entity, FromResource, Patchable, SingleEntity};
+use rabbithole::include::IncludeLoaders;
+use rabbithole::model::document::Document;
+use rabbithole::model::error::{Error, ErrorSource, Errors};
+use rabbithole::model::relationship::Relationship;
+use rabbithole::model::resource::ResourceIdentifier;
+use rabbithole::model::JsonApiInfo;
 use rabbithole::operation::{
-    Creating, Deleting, Fetching, IdentifierDataWrapper, OperationResultData, ResourceDataWrapper,
+    Created, Creating, Deleting, Fetching, IdentifierDataWrapper, OperationResultData,
+    ResourceDataWrapper, Updating,
 };
-use serde::Deserialize;
+use rabbithole::policy::{Action, AllowAll, BoundPolicy, FieldVisibility, Policy, PolicyContext};
+use rabbithole::query::Query;
+use rabbithole::validation::Validate;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 #[derive(Deserialize, Debug, Clone)]
@@ -18,78 +29,167 @@ pub struct ActixSettings {
     pub port: u32,
     pub jsonapi: crate::settings::JsonApiSettings,
     pub query: rabbithole::query::QuerySettings,
+    #[serde(default)]
+    pub errors: crate::settings::ErrorSettings,
 }
 
 macro_rules! to_response {
-    (Resource: $this:ident, $item:ident) => {{
+    (Resource: $this:ident, $req:ident, $query:ident, $policy:ident, $ctx:ident, $item:ident) => {{
         let OperationResultData {
             data,
             additional_links,
             additional_meta,
         } = $item;
-        let resource = data.to_resource(&$this.uri().to_string(), &Default::default());
-        match resource {
-            Some(mut resource) => {
-                resource.extend_meta(additional_meta);
-                resource.extend_links(additional_links);
-                Ok(HttpResponse::Ok().json(ResourceDataWrapper { data: resource }))
+        let uri = &$this.uri().to_string();
+        match data {
+            Some(data) => with_included(&$req, uri, &$query, |query| {
+                SingleEntity::to_document(
+                    &data,
+                    uri,
+                    query,
+                    $req.uri().to_owned(),
+                    additional_links,
+                    additional_meta,
+                )
+            })
+            .await
+            .map(|doc| $policy.filter_included(&$ctx, doc))
+            .map_or_else(
+                |e| ActixRabbitholeError::from(e).into(),
+                |doc| Ok(HttpResponse::Ok().json($this.with_jsonapi(doc))),
+            ),
+            None => {
+                if additional_links.is_empty() && additional_meta.is_empty() {
+                    Ok(HttpResponse::NoContent().finish())
+                } else {
+                    Ok(HttpResponse::Ok().json(
+                        $this.with_jsonapi(Document::null(additional_links, additional_meta)),
+                    ))
+                }
             },
-            None => Ok(HttpResponse::NoContent().finish()),
         }
     }};
-    (Relationship: $this:ident, $item:ident) => {{
+    (Relationship: $this:ident, $req:ident, $query:ident, $policy:ident, $ctx:ident, $item:ident) => {{
         let OperationResultData {
             data,
             additional_links,
             additional_meta,
         } = $item;
         let (field_name, item) = data;
-        let resource = item.to_resource(&$this.uri().to_string(), &Default::default());
-        match resource {
-            Some(mut resource) => {
-                resource.extend_meta(additional_meta);
-                resource.extend_links(additional_links);
-
-                Ok(HttpResponse::Ok().json(IdentifierDataWrapper {
-                    data: resource
-                        .relationships
-                        .get(&field_name)
-                        .cloned()
-                        .unwrap()
-                        .data,
-                }))
+        let resource = item.to_resource(
+            &$this.uri().to_string(),
+            &Default::default(),
+            $query.visibility(),
+        );
+        match resource.and_then(|mut resource| resource.relationships.remove(&field_name)) {
+            Some(relat) => {
+                let mut relat = $policy.filter_relationship(&$ctx, relat);
+                relat.extend_links(additional_links);
+                relat.extend_meta(additional_meta);
+                Ok(HttpResponse::Ok().json($this.relationship_document(relat)))
             },
+            // The resource is left as the client requested, or the field is hidden from the client
             None => Ok(HttpResponse::NoContent().finish()),
         }
     }};
 }
 
 macro_rules! single_step_operation {
-    ($return_ty:ident:  $fn_name:ident, $mark:ident, $( $param:ident => $ty:ty ),+) => {
-        pub async fn $fn_name<T>(this: web::Data<Self>, service: web::Data<std::sync::Arc<futures::lock::Mutex<T>>>, req: actix_web::HttpRequest, $($param: $ty),+)
+    ($return_ty:ident:  $fn_name:ident, $mark:ident, $action:ident, $params:ident => $params_ty:ty, $body:ident => $body_ty:ty) => {
+        pub async fn $fn_name<T>(this: web::Data<Self>, service: web::Data<std::sync::Arc<futures::lock::Mutex<T>>>, req: actix_web::HttpRequest, $params: $params_ty, $body: $body_ty)
           -> actix_web::Result<HttpResponse>
             where
                 T: 'static + rabbithole::operation::$mark + Send + Sync,
                 T::Item: SingleEntity + Send + Sync,
           {
-            match service.lock().await.$fn_name($(&$param),+, &this.uri().to_string(), &req.uri()).await {
+            let (policy, ctx) = match authorize::<T::Item>(&req, Action::$action).await {
+                Ok(res) => res,
+                Err(err) => return err.into(),
+            };
+            let (id, field) = $params.into_inner();
+            let $params = match parse_id::<T::Item>(&id) {
+                Ok(id) => (id, field),
+                Err(err) => return err.into(),
+            };
+            let mut query = match this.query.decode_path(req.uri()) {
+                Ok(query) => query,
+                Err(err) => return ActixRabbitholeError::from(err).into(),
+            };
+            query.visibility = policy.field_visibility(&ctx);
+            if let Err(err) = $body
+                .check_identity::<T::Item>(&ctx)
+                .and_then(|_| $body.check_writable::<T::Item>(query.visibility(), &ctx))
+            {
+                return ActixRabbitholeError::from(err).into();
+            }
+            match service.lock().await.$fn_name(&$params, &$body, &this.uri().to_string(), &req.uri()).await {
                 Ok(item) => {
-                    to_response!($return_ty: this, item)
+                    to_response!($return_ty: this, req, query, policy, ctx, item)
                 },
-                Err(err) => ActixRabbitholeError(err).into(),
+                Err(err) => ActixRabbitholeError::from(err).into(),
             }
         }
     };
 }
 
 impl ActixSettings {
-    single_step_operation!(Resource: update_resource, Updating, params => web::Path<String>, body => web::Json<ResourceDataWrapper>);
+    single_step_operation!(Relationship: replace_relationship, Updating, ReplaceRelationship, params => web::Path<(String, String)>, body => web::Json<IdentifierDataWrapper>);
+
+    single_step_operation!(Relationship: add_relationship, Updating, AddRelationship, params => web::Path<(String, String)>, body => web::Json<IdentifierDataWrapper>);
 
-    single_step_operation!(Relationship: replace_relationship, Updating, params => web::Path<(String, String)>, body => web::Json<IdentifierDataWrapper>);
+    single_step_operation!(Relationship: remove_relationship, Updating, RemoveRelationship, params => web::Path<(String, String)>, body => web::Json<IdentifierDataWrapper>);
 
-    single_step_operation!(Relationship: add_relationship, Updating, params => web::Path<(String, String)>, body => web::Json<IdentifierDataWrapper>);
+    /// Decodes the body into the patch of the entity, where the service resolves the related
+    /// entities of the relationships as the `EntityLookup` of their types
+    pub async fn update_resource<T>(
+        this: web::Data<Self>,
+        service: web::Data<Arc<Mutex<T>>>,
+        req: actix_web::HttpRequest,
+        params: web::Path<String>,
+        body: web::Json<ResourceDataWrapper>,
+    ) -> actix_web::Result<HttpResponse>
+    where
+        T: 'static + Updating + Send + Sync,
+        T::Item: Patchable + Validate + Send + Sync,
+        <T::Item as Patchable>::Patch: FromResource<T>,
+    {
+        let (policy, ctx) = match authorize::<T::Item>(&req, Action::UpdateResource).await {
+            Ok(res) => res,
+            Err(err) => return err.into(),
+        };
+        let id = match parse_id::<T::Item>(&params) {
+            Ok(id) => id,
+            Err(err) => return err.into(),
+        };
+        let mut query = match this.query.decode_path(req.uri()) {
+            Ok(query) => query,
+            Err(err) => return ActixRabbitholeError::from(err).into(),
+        };
+        query.visibility = policy.field_visibility(&ctx);
+        if let Err(err) = body
+            .check_identity::<T::Item>(&ctx)
+            .and_then(|_| body.check_writable::<T::Item>(query.visibility(), &ctx))
+        {
+            return ActixRabbitholeError::from(err).into();
+        }
+        if let Err(errors) = T::Item::validate(&body.data, true) {
+            return ActixRabbitholeError(errors).into();
+        }
 
-    single_step_operation!(Relationship: remove_relationship, Updating, params => web::Path<(String, String)>, body => web::Json<IdentifierDataWrapper>);
+        let mut service = service.lock().await;
+        // The service itself resolves the relationships of the patch
+        let patch = match FromResource::from_resource(&body.data, &*service).await {
+            Ok(patch) => patch,
+            Err(err) => return ActixRabbitholeError::from(err).into(),
+        };
+        match service
+            .update_resource(&id, patch, &this.uri().to_string(), req.uri())
+            .await
+        {
+            Ok(item) => to_response!(Resource: this, req, query, policy, ctx, item),
+            Err(err) => ActixRabbitholeError::from(err).into(),
+        }
+    }
 
     fn uri(&self) -> url::Url {
         format!("http://{}:{}", self.host, self.port)
@@ -99,6 +199,24 @@ impl ActixSettings {
             .unwrap()
     }
 
+    fn with_jsonapi(&self, mut doc: Document) -> Document {
+        doc.jsonapi = Some(JsonApiInfo {
+            version: Some(self.jsonapi.version.clone()),
+            meta: None,
+        });
+        doc
+    }
+
+    fn relationship_document(&self, relationship: Relationship) -> RelationshipDocument {
+        RelationshipDocument {
+            relationship,
+            jsonapi: JsonApiInfo {
+                version: Some(self.jsonapi.version.clone()),
+                meta: None,
+            },
+        }
+    }
+
     pub async fn delete_resource<T>(
         this: web::Data<Self>,
         service: web::Data<Arc<Mutex<T>>>,
@@ -109,10 +227,18 @@ impl ActixSettings {
         T: 'static + Deleting + Send + Sync,
         T::Item: SingleEntity + Send + Sync,
     {
+        if let Err(err) = authorize::<T::Item>(&req, Action::DeleteResource).await {
+            return err.into();
+        }
+        let id = match parse_id::<T::Item>(&params) {
+            Ok(id) => id,
+            Err(err) => return err.into(),
+        };
+
         match service
             .lock()
             .await
-            .delete_resource(&params, &this.uri().to_string(), &req.uri())
+            .delete_resource(&id, &this.uri().to_string(), &req.uri())
             .await
         {
             Ok(OperationResultData {
@@ -123,15 +249,11 @@ impl ActixSettings {
                 if additional_links.is_empty() && additional_meta.is_empty() {
                     Ok(HttpResponse::NoContent().finish())
                 } else {
-                    Ok(
-                        HttpResponse::Ok().json(rabbithole::model::document::Document::null(
-                            additional_links,
-                            additional_meta,
-                        )),
-                    )
+                    Ok(HttpResponse::Ok()
+                        .json(this.with_jsonapi(Document::null(additional_links, additional_meta))))
                 }
             },
-            Err(err) => ActixRabbitholeError(err).into(),
+            Err(err) => ActixRabbitholeError::from(err).into(),
         }
     }
 
@@ -143,8 +265,32 @@ impl ActixSettings {
     ) -> actix_web::Result<HttpResponse>
     where
         T: 'static + Creating + Send + Sync,
-        T::Item: SingleEntity + Send + Sync,
+        T::Item: SingleEntity + Validate + Send + Sync,
     {
+        let (policy, ctx) = match authorize::<T::Item>(&req, Action::Create).await {
+            Ok(res) => res,
+            Err(err) => return err.into(),
+        };
+        let mut query = match this.query.decode_path(req.uri()) {
+            Ok(query) => query,
+            Err(err) => return ActixRabbitholeError::from(err).into(),
+        };
+        query.visibility = policy.field_visibility(&ctx);
+        if let Err(err) = body
+            .check_identity::<T::Item>(&ctx)
+            .and_then(|_| {
+                this.jsonapi
+                    .client_generated_id
+                    .check(&ctx.ty, &body.data.id.id)
+            })
+            .and_then(|_| body.check_writable::<T::Item>(query.visibility(), &ctx))
+        {
+            return ActixRabbitholeError::from(err).into();
+        }
+        if let Err(errors) = T::Item::validate(&body.data, false) {
+            return ActixRabbitholeError(errors).into();
+        }
+
         let uri = &this.uri().to_string();
 
         match service
@@ -154,16 +300,56 @@ impl ActixSettings {
             .await
         {
             Ok(OperationResultData {
-                data,
+                data: Created::Resource(data),
+                additional_links,
+                additional_meta,
+            }) => {
+                let location = data.links(uri).remove("self");
+                with_included(&req, uri, &query, |query| {
+                    SingleEntity::to_document(
+                        &data,
+                        uri,
+                        query,
+                        req.uri().to_owned(),
+                        additional_links,
+                        additional_meta,
+                    )
+                })
+                .await
+                .map(|doc| policy.filter_included(&ctx, doc))
+                .map_or_else(
+                    |e| ActixRabbitholeError::from(e).into(),
+                    |mut doc| {
+                        let mut resp = HttpResponse::Created();
+                        // The created resource is the one the document is about
+                        if let Some(location) = location {
+                            resp.header(
+                                actix_web::http::header::LOCATION,
+                                http::Uri::from(&location).to_string(),
+                            );
+                            doc.links.insert("self".into(), location);
+                        }
+                        Ok(resp.json(this.with_jsonapi(doc)))
+                    },
+                )
+            },
+            Ok(OperationResultData {
+                data: Created::Accepted,
                 additional_links,
                 additional_meta,
             }) => {
-                let mut resource = data.to_resource(uri, &Default::default()).unwrap();
-                resource.extend_meta(additional_meta);
-                resource.extend_links(additional_links);
-                Ok(HttpResponse::Ok().json(ResourceDataWrapper { data: resource }))
+                if additional_links.is_empty() && additional_meta.is_empty() {
+                    Ok(HttpResponse::Accepted().finish())
+                } else {
+                    Ok(HttpResponse::Accepted()
+                        .json(this.with_jsonapi(Document::null(additional_links, additional_meta))))
+                }
             },
-            Err(err) => ActixRabbitholeError(err).into(),
+            Ok(OperationResultData {
+                data: Created::NoContent,
+                ..
+            }) => Ok(HttpResponse::NoContent().finish()),
+            Err(err) => ActixRabbitholeError::from(err).into(),
         }
     }
 
@@ -176,12 +362,22 @@ impl ActixSettings {
         T: 'static + Fetching + Send + Sync,
         T::Item: SingleEntity + Send + Sync,
     {
+        let (policy, ctx) = match authorize::<T::Item>(&req, Action::FetchCollection).await {
+            Ok(res) => res,
+            Err(err) => return err.into(),
+        };
+
         let path = req.uri().clone();
 
-        let query = this
+        let mut query = this
             .query
             .decode_path(&path)
-            .map_err(ActixRabbitholeError)?;
+            .map_err(ActixRabbitholeError::from)?;
+        query.visibility = policy.field_visibility(&ctx);
+        query.policy = Some(BoundPolicy {
+            policy: policy.clone(),
+            ctx: ctx.clone(),
+        });
 
         let uri = &this.uri().to_string();
 
@@ -195,13 +391,16 @@ impl ActixSettings {
                 data,
                 additional_links,
                 additional_meta,
-            }) => data
-                .to_document(uri, &query, path, additional_links, additional_meta)
-                .map_or_else(
-                    |e| ActixRabbitholeError(e).into(),
-                    |v| Ok(HttpResponse::Ok().json(v)),
-                ),
-            Err(err) => ActixRabbitholeError(err).into(),
+            }) => with_included(&req, uri, &query, |query| {
+                data.to_document(uri, query, path, additional_links, additional_meta)
+            })
+            .await
+            .and_then(|doc| policy.filter_document(&ctx, doc))
+            .map_or_else(
+                |e| ActixRabbitholeError::from(e).into(),
+                |v| Ok(HttpResponse::Ok().json(this.with_jsonapi(v))),
+            ),
+            Err(err) => ActixRabbitholeError::from(err).into(),
         }
     }
 
@@ -215,36 +414,52 @@ impl ActixSettings {
         T: 'static + Fetching + Send + Sync,
         T::Item: SingleEntity + Send + Sync,
     {
+        let (policy, ctx) = match authorize::<T::Item>(&req, Action::FetchSingle).await {
+            Ok(res) => res,
+            Err(err) => return err.into(),
+        };
+        let id = match parse_id::<T::Item>(&param) {
+            Ok(id) => id,
+            Err(err) => return err.into(),
+        };
+
         let path = req.uri().clone();
 
-        let query = this
+        let mut query = this
             .query
             .decode_path(&path)
-            .map_err(ActixRabbitholeError)?;
+            .map_err(ActixRabbitholeError::from)?;
+        query.visibility = policy.field_visibility(&ctx);
+
+        let uri = &this.uri().to_string();
 
         match service
             .lock()
             .await
-            .fetch_single(&param, &this.uri().to_string(), &path, &query)
+            .fetch_single(&id, uri, &path, &query)
             .await
         {
             Ok(OperationResultData {
                 data,
                 additional_links,
                 additional_meta,
-            }) => SingleEntity::to_document(
-                &data,
-                &this.uri().to_string(),
-                &query,
-                req.uri().to_owned(),
-                additional_links,
-                additional_meta,
-            )
+            }) => with_included(&req, uri, &query, |query| {
+                SingleEntity::to_document(
+                    &data,
+                    uri,
+                    query,
+                    req.uri().to_owned(),
+                    additional_links,
+                    additional_meta,
+                )
+            })
+            .await
+            .and_then(|doc| policy.filter_document(&ctx, doc))
             .map_or_else(
-                |e| ActixRabbitholeError(e).into(),
-                |v| Ok(HttpResponse::Ok().json(v)),
+                |e| ActixRabbitholeError::from(e).into(),
+                |v| Ok(HttpResponse::Ok().json(this.with_jsonapi(v))),
             ),
-            Err(err) => ActixRabbitholeError(err).into(),
+            Err(err) => ActixRabbitholeError::from(err).into(),
         }
     }
 
@@ -258,14 +473,27 @@ impl ActixSettings {
         T: 'static + Fetching + Send + Sync,
         T::Item: SingleEntity + Send + Sync,
     {
+        let (policy, ctx) = match authorize::<T::Item>(&req, Action::FetchRelationship).await {
+            Ok(res) => res,
+            Err(err) => return err.into(),
+        };
+
         let path = req.uri().clone();
 
-        let query = this
+        let mut query = this
             .query
             .decode_path(&path)
-            .map_err(ActixRabbitholeError)?;
+            .map_err(ActixRabbitholeError::from)?;
+        query.visibility = policy.field_visibility(&ctx);
 
         let (id, related_field) = param.into_inner();
+        let id = match parse_id::<T::Item>(&id) {
+            Ok(id) => id,
+            Err(err) => return err.into(),
+        };
+        if !query.visibility().can_read_field(&ctx.ty, &related_field) {
+            return ActixRabbitholeError::from(Error::FieldNotExist(&related_field, None)).into();
+        }
 
         match service
             .lock()
@@ -274,18 +502,57 @@ impl ActixSettings {
             .await
         {
             Ok(OperationResultData {
-                mut data,
+                data,
                 additional_links,
                 additional_meta,
             }) => {
+                let mut data = policy.filter_relationship(&ctx, data);
                 data.extend_links(additional_links);
                 data.extend_meta(additional_meta);
-                Ok(HttpResponse::Ok().json(data))
+                Ok(HttpResponse::Ok().json(this.relationship_document(data)))
             },
-            Err(err) => ActixRabbitholeError(err).into(),
+            Err(err) => ActixRabbitholeError::from(err).into(),
         }
     }
 
+    /// The `JsonConfig` of the request bodies, which responds the errors as JSON:API documents
+    pub fn json_config() -> web::JsonConfig {
+        web::JsonConfig::default().error_handler(|err, _req| {
+            let err = match err {
+                JsonPayloadError::Deserialize(err) => Error::InvalidJson(&err, None),
+                err => Error::InvalidRequestBody(&err.to_string(), None),
+            };
+            ActixRabbitholeError::from(err).into()
+        })
+    }
+
+    /// The `PathConfig` of the path parameters, which responds the errors as JSON:API documents
+    pub fn path_config() -> web::PathConfig {
+        web::PathConfig::default().error_handler(|err, _req| {
+            ActixRabbitholeError::from(Error::InvalidPathParameter(&err.to_string(), None)).into()
+        })
+    }
+
+    /// The default service for the paths matching no route
+    pub async fn route_not_found(req: HttpRequest) -> actix_web::Result<HttpResponse> {
+        ActixRabbitholeError::from(Error::RouteNotFound(req.path(), None)).into()
+    }
+
+    /// The default service for the routes not accepting the method, responding the `allowed`
+    /// methods of the route in the `Allow` header
+    pub async fn method_not_allowed(
+        req: HttpRequest,
+        allowed: &'static [&'static str],
+    ) -> actix_web::Result<HttpResponse> {
+        ActixRabbitholeError::from(Error::MethodNotAllowed(
+            req.method().as_str(),
+            req.path(),
+            allowed,
+            None,
+        ))
+        .into()
+    }
+
     pub async fn fetch_related<T>(
         this: web::Data<Self>,
         service: web::Data<Arc<Mutex<T>>>,
@@ -296,28 +563,222 @@ impl ActixSettings {
         T: 'static + Fetching + Send + Sync,
         T::Item: SingleEntity + Send + Sync,
     {
+        let (policy, ctx) = match authorize::<T::Item>(&req, Action::FetchRelated).await {
+            Ok(res) => res,
+            Err(err) => return err.into(),
+        };
+
         let path = req.uri().clone();
 
-        let query = this
+        let mut query = this
             .query
             .decode_path(&path)
-            .map_err(ActixRabbitholeError)?;
+            .map_err(ActixRabbitholeError::from)?;
+        query.visibility = policy.field_visibility(&ctx);
 
         let (id, related_field) = param.into_inner();
+        let id = match parse_id::<T::Item>(&id) {
+            Ok(id) => id,
+            Err(err) => return err.into(),
+        };
+        if !query.visibility().can_read_field(&ctx.ty, &related_field) {
+            return ActixRabbitholeError::from(Error::FieldNotExist(&related_field, None)).into();
+        }
 
-        service
+        let uri = &this.uri().to_string();
+        let doc = match service
             .lock()
             .await
-            .fetch_related(&id, &related_field, &this.uri().to_string(), &path, &query)
+            .fetch_related(&id, &related_field, uri, &path, &build_query(&req, &query))
             .await
-            .map(|v| HttpResponse::Ok().json(v))
-            .map_err(|e| ActixRabbitholeError(e).into())
+        {
+            Ok(doc) => doc,
+            Err(err) => return ActixRabbitholeError::from(err).into(),
+        };
+        include_nested(&req, doc, uri, &query)
+            .await
+            .and_then(|doc| policy.filter_document(&ctx, doc))
+            .map_or_else(
+                |e| ActixRabbitholeError::from(e).into(),
+                |v| Ok(HttpResponse::Ok().json(this.with_jsonapi(v))),
+            )
+    }
+}
+
+/// Request bodies which are checked against the route and the `FieldVisibility` of the client
+/// before being handed to the services
+trait WritableBody {
+    /// Checks the `type` and `id` of the primary data against the route of the entity `E`
+    #[allow(unused_variables)]
+    fn check_identity<E: SingleEntity>(&self, ctx: &PolicyContext) -> rabbithole::Result<()> {
+        Ok(())
+    }
+
+    /// Checks the fields written by the body against the entity `E` and the `FieldVisibility`
+    fn check_writable<E: SingleEntity>(
+        &self,
+        visibility: &dyn FieldVisibility,
+        ctx: &PolicyContext,
+    ) -> rabbithole::Result<()>;
+}
+
+impl WritableBody for ResourceDataWrapper {
+    fn check_identity<E: SingleEntity>(&self, ctx: &PolicyContext) -> rabbithole::Result<()> {
+        let ResourceIdentifier { ty, id } = &self.data.id;
+        if !E::accepts_type(ty) {
+            return Err(Error::TypeNotMatch(
+                &ctx.ty,
+                ty,
+                Some(ErrorSource::from_pointer("/data/type")),
+            ));
+        }
+        match &ctx.id {
+            Some(expected) if expected != id => Err(Error::IdNotMatch(
+                expected,
+                id,
+                Some(ErrorSource::from_pointer("/data/id")),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_writable<E: SingleEntity>(
+        &self,
+        visibility: &dyn FieldVisibility,
+        ctx: &PolicyContext,
+    ) -> rabbithole::Result<()> {
+        // The read-only fields are left to `Validate`, which points to all of them at once
+        visibility.check_writable(&ctx.ty, &self.data)
     }
 }
 
+impl WritableBody for IdentifierDataWrapper {
+    fn check_writable<E: SingleEntity>(
+        &self,
+        visibility: &dyn FieldVisibility,
+        ctx: &PolicyContext,
+    ) -> rabbithole::Result<()> {
+        match &ctx.related_field {
+            Some(field)
+                if !E::is_writable(field) || !visibility.can_write_field(&ctx.ty, field) =>
+            {
+                Err(Error::FieldNotWritable(
+                    field,
+                    Some(ErrorSource::from_pointer("/data")),
+                ))
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Builds the document of a response with `build`, whose nested `include` paths are resolved by
+/// the `IncludeLoaders` registered with `App::data::<IncludeLoaders>`, if any
+async fn with_included(
+    req: &HttpRequest,
+    uri: &str,
+    query: &Query,
+    build: impl FnOnce(&Query) -> rabbithole::Result<Document>,
+) -> rabbithole::Result<Document> {
+    let doc = build(&build_query(req, query))?;
+    include_nested(req, doc, uri, query).await
+}
+
+/// The query to build a document with, which is completed by `include_nested`
+fn build_query(req: &HttpRequest, query: &Query) -> Query {
+    match req.app_data::<web::Data<IncludeLoaders>>() {
+        Some(_) => query.shallow_include(),
+        None => query.clone(),
+    }
+}
+
+/// Resolves the nested `include` paths of `doc` built with `build_query`
+async fn include_nested(
+    req: &HttpRequest,
+    doc: Document,
+    uri: &str,
+    query: &Query,
+) -> rabbithole::Result<Document> {
+    match req.app_data::<web::Data<IncludeLoaders>>() {
+        Some(loaders) => loaders.include(doc, uri, query).await,
+        None => Ok(doc),
+    }
+}
+
+/// Consults the `Policy` registered with `App::data::<Arc<dyn Policy>>`, or `AllowAll` if there
+/// is none, about the coming `action`
+async fn authorize<E: SingleEntity>(
+    req: &HttpRequest,
+    action: Action,
+) -> Result<(Arc<dyn Policy>, PolicyContext), ActixRabbitholeError> {
+    let policy: Arc<dyn Policy> = req
+        .app_data::<web::Data<Arc<dyn Policy>>>()
+        .map(|p| p.get_ref().clone())
+        .unwrap_or_else(|| Arc::new(AllowAll));
+    let ctx = PolicyContext {
+        action,
+        ty: E::ty(),
+        id: req.match_info().get("id").map(ToString::to_string),
+        related_field: req
+            .match_info()
+            .get("related_fields")
+            .map(ToString::to_string),
+        headers: http_headers(req.headers()),
+    };
+    policy
+        .authorize(&ctx)
+        .await
+        .map_err(|denial| ActixRabbitholeError::from(denial.into_error(&ctx)))?;
+    Ok((policy, ctx))
+}
+
+/// Parses the `id` in the route of `req` into the id type of `E`
+fn parse_id<E: SingleEntity>(id: &str) -> Result<E::Id, ActixRabbitholeError> {
+    E::parse_id(id).map_err(ActixRabbitholeError::from)
+}
+
+/// The headers of a request as the `http::HeaderMap` the services know
+fn http_headers(headers: &actix_web::http::HeaderMap) -> http::HeaderMap {
+    headers
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect()
+}
+
+/// The errors of an operation, which are responded in one `errors` document
+/// The top-level document of the relationship routes, which is the relationship with the
+/// `jsonapi` object like the other documents
+#[derive(Serialize)]
+struct RelationshipDocument {
+    #[serde(flatten)]
+    relationship: Relationship,
+    jsonapi: JsonApiInfo,
+}
+
 #[derive(Debug)]
-struct ActixRabbitholeError(rabbithole::model::error::Error);
-impl actix_http::error::ResponseError for ActixRabbitholeError {}
+struct ActixRabbitholeError(Errors);
+
+impl From<Error> for ActixRabbitholeError {
+    fn from(err: Error) -> Self { Self(err.into()) }
+}
+
+impl From<Errors> for ActixRabbitholeError {
+    fn from(errors: Errors) -> Self { Self(errors) }
+}
+
+/// The errors of the handlers and the extractors, which are rendered by the `JsonApi` middleware
+impl actix_http::error::ResponseError for ActixRabbitholeError {
+    fn status_code(&self) -> actix_web::http::StatusCode { self.0.status() }
+
+    fn error_response(&self) -> HttpResponse {
+        let mut resp = HttpResponse::build(self.status_code());
+        for (name, value) in self.0.iter().filter_map(Error::headers).flatten() {
+            resp.header(name.clone(), value.clone());
+        }
+        resp.content_type(rabbithole::JSON_API_HEADER)
+            .body(serde_json::to_string(&Document::from(self.0.clone())).unwrap())
+    }
+}
 
 use std::fmt;
 impl fmt::Display for ActixRabbitholeError {
@@ -325,14 +786,5 @@ impl fmt::Display for ActixRabbitholeError {
 }
 
 impl From<ActixRabbitholeError> for Result<HttpResponse, actix_web::Error> {
-    fn from(err: ActixRabbitholeError) -> Self {
-        Ok(HttpResponse::build(
-            err.0
-                .status
-                .as_deref()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(actix_web::http::StatusCode::BAD_REQUEST),
-        )
-        .body(serde_json::to_string(&err.0).unwrap()))
-    }
+    fn from(err: ActixRabbitholeError) -> Self { Err(err.into()) }
 }
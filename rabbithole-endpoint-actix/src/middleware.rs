@@ -1,11 +1,16 @@
-use super::ActixSettings;
+use super::{ActixRabbitholeError, ActixSettings};
 use actix_service::{Service, Transform};
+use actix_web::dev::{Body, ResponseBody, ServiceRequest, ServiceResponse};
 use actix_web::http::header;
-use actix_web::{dev::ServiceRequest, dev::ServiceResponse, web, Error};
+use actix_web::{web, Error};
 use futures::future::{ok, Ready};
 use futures::Future;
+use rabbithole::model::document::Document;
+use rabbithole::model::error::Errors;
+use rabbithole::render::ErrorRenderer;
 use rabbithole::rule::RuleDispatcher;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 // There are two steps in middleware processing.
@@ -54,6 +59,9 @@ where
 
     fn call(&mut self, req: ServiceRequest) -> Self::Future {
         let settings: web::Data<ActixSettings> = req.app_data().unwrap();
+        let renderer = req
+            .app_data::<Arc<dyn ErrorRenderer>>()
+            .map(|renderer| renderer.get_ref().clone());
         let api_version = &settings.get_ref().jsonapi.version;
         let headers = req.headers();
         let content_type = headers
@@ -64,8 +72,8 @@ where
             .map(|r| r.to_str().unwrap().to_string());
 
         if let Err(e) = RuleDispatcher::ContentTypeMustBeJsonApi(api_version, &content_type) {
-            let mut res =
-                req.into_response(HttpResponse::UnsupportedMediaType().json(e).into_body());
+            let doc = render(&settings, renderer.as_deref(), req.headers(), e.into());
+            let mut res = req.into_response(HttpResponse::UnsupportedMediaType().json(doc).into_body());
             res.headers_mut().insert(
                 header::CONTENT_TYPE,
                 rabbithole::JSON_API_HEADER.parse().unwrap(),
@@ -74,7 +82,8 @@ where
         }
 
         if let Err(e) = RuleDispatcher::AcceptHeaderShouldBeJsonApi(api_version, &accept) {
-            let mut res = req.into_response(HttpResponse::NotAcceptable().json(e).into_body());
+            let doc = render(&settings, renderer.as_deref(), req.headers(), e.into());
+            let mut res = req.into_response(HttpResponse::NotAcceptable().json(doc).into_body());
             res.headers_mut().insert(
                 header::CONTENT_TYPE,
                 rabbithole::JSON_API_HEADER.parse().unwrap(),
@@ -87,6 +96,19 @@ where
         Box::pin(async move {
             let mut res = fut.await?;
 
+            // The errors responded by the endpoints are rendered here, once for all of them
+            let errors = res
+                .response()
+                .error()
+                .and_then(|err| err.as_error::<ActixRabbitholeError>())
+                .map(|err| err.0.clone());
+            if let Some(errors) = errors {
+                let headers = res.request().headers();
+                let doc = render(&settings, renderer.as_deref(), headers, errors);
+                let body = serde_json::to_string(&doc).unwrap();
+                res = res.map_body(|_, _| ResponseBody::Other(Body::from(body)));
+            }
+
             res.headers_mut().insert(
                 header::CONTENT_TYPE,
                 rabbithole::JSON_API_HEADER.parse().unwrap(),
@@ -98,3 +120,16 @@ where
 }
 
 use actix_web::HttpResponse;
+
+/// Links the errors to their documents, then renders them with the `ErrorRenderer` registered in
+/// the application, if any
+fn render(
+    settings: &ActixSettings, renderer: Option<&dyn ErrorRenderer>, headers: &header::HeaderMap,
+    errors: Errors,
+) -> Document {
+    let errors = settings.errors.link_docs(errors);
+    match renderer {
+        Some(renderer) => renderer.render(&super::http_headers(headers), errors).into(),
+        None => errors.into(),
+    }
+}
@@ -1,7 +1,68 @@
+use rabbithole::model::error::{Error, ErrorSource, Errors};
 use rabbithole::model::version::JsonApiVersion;
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct JsonApiSettings {
     pub version: JsonApiVersion,
+    #[serde(default)]
+    pub client_generated_id: ClientGeneratedId,
+}
+
+/// Whether the clients can assign the ids of the resources they create
+#[derive(Debug, Deserialize, Clone, Copy, Eq, PartialEq, Default)]
+pub enum ClientGeneratedId {
+    /// The id in the body is optional, the services generate one if it is missing
+    #[default]
+    Accept,
+    /// The clients cannot send an id, responding `403 Forbidden` otherwise
+    Forbid,
+    /// The clients should always send an id, responding `400 Bad Request` otherwise
+    Require,
+}
+
+impl ClientGeneratedId {
+    /// Checks the `id` in the body of a creation request of resource `ty`, which is empty if missing
+    pub fn check(self, ty: &str, id: &str) -> rabbithole::Result<()> {
+        match self {
+            ClientGeneratedId::Forbid if !id.is_empty() => Err(Error::ClientIdNotSupported(
+                ty,
+                Some(ErrorSource::from_pointer("/data/id")),
+            )),
+            ClientGeneratedId::Require if id.is_empty() => Err(Error::ClientIdRequired(
+                ty,
+                Some(ErrorSource::from_pointer("/data/id")),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// How the errors are responded
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ErrorSettings {
+    /// The base URL of the documents of the errors, making `<docs_url>/RBH-xxxx` the `about`
+    /// link of every error of Rabbithole
+    #[serde(default)]
+    pub docs_url: Option<String>,
+}
+
+impl ErrorSettings {
+    /// Links the errors of Rabbithole without an `about` link to their documents
+    pub fn link_docs(&self, mut errors: Errors) -> Errors {
+        if let Some(docs_url) = &self.docs_url {
+            for err in errors.0.iter_mut() {
+                match &err.code {
+                    Some(code) if code.starts_with("RBH-") && err.links.about().is_none() => {
+                        let about = format!("{}/{}", docs_url.trim_end_matches('/'), code);
+                        if let Ok(about) = about.parse() {
+                            err.links.set_about(about);
+                        }
+                    },
+                    _ => {},
+                }
+            }
+        }
+        errors
+    }
 }
@@ -1,5 +1,5 @@
 use actix_web::http::{header, StatusCode};
-use actix_web::test::call_service;
+use actix_web::test::{call_service, read_body};
 use actix_web::test::TestRequest;
 use rabbithole::JSON_API_HEADER;
 use rabbithole_endpoint_actix::ActixSettings;
@@ -21,6 +21,9 @@ async fn invalid_accept_header_test() {
 
     let resp = call_service(&mut app, req).await;
     assert_eq!(resp.status(), StatusCode::NOT_ACCEPTABLE);
+    assert_eq!(resp.headers().get(header::CONTENT_TYPE).unwrap(), JSON_API_HEADER);
+    let doc: serde_json::Value = serde_json::from_slice(&read_body(resp).await).unwrap();
+    assert_eq!(doc["errors"][0]["code"], "RBH-0302");
 }
 
 #[actix_rt::test]
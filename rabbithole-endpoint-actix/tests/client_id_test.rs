@@ -0,0 +1,75 @@
+#[macro_use]
+extern crate lazy_static;
+
+pub mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test::{call_service, read_response_json};
+use common::model::dog::generate_dogs;
+use common::service;
+use common::{patch, post};
+use rabbithole::operation::ResourceDataWrapper;
+use rabbithole_endpoint_actix::ActixSettings;
+
+#[actix_rt::test]
+/// https://jsonapi.org/format/#crud-creating-responses-409
+async fn conflict_test() {
+    let mut app = init_app!(1, 1);
+
+    let dogs = generate_dogs(2);
+    let dog_resources = ResourceDataWrapper::from_entities(&dogs, "http://localhost:1234/api/v1");
+    let req = post("/api/v1/dogs", &dog_resources[0]);
+    let resp = call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    // The same id again
+    let req = post("/api/v1/dogs", &dog_resources[0]);
+    let err: serde_json::Value = read_response_json(&mut app, req).await;
+    assert_eq!(err["errors"][0]["status"], "409");
+    assert_eq!(err["errors"][0]["code"], "RBH-0207");
+
+    // A dog posted to the people
+    let req = post("/api/v1/people", &dog_resources[1]);
+    let err: serde_json::Value = read_response_json(&mut app, req).await;
+    assert_eq!(err["errors"][0]["status"], "409");
+    assert_eq!(err["errors"][0]["code"], "RBH-0203");
+    assert_eq!(err["errors"][0]["source"]["pointer"], "/data/type");
+
+    // The id in the body differs from the one in the URL
+    let req = patch(
+        format!("/api/v1/dogs/{}", dogs[0].id).as_str(),
+        &dog_resources[1],
+    );
+    let err: serde_json::Value = read_response_json(&mut app, req).await;
+    assert_eq!(err["errors"][0]["status"], "409");
+    assert_eq!(err["errors"][0]["code"], "RBH-0204");
+    assert_eq!(err["errors"][0]["source"]["pointer"], "/data/id");
+
+    let req = patch(
+        format!("/api/v1/dogs/{}", dogs[0].id).as_str(),
+        &dog_resources[0],
+    );
+    let resp = call_service(&mut app, req).await;
+    assert!(resp.status().is_success());
+}
+
+#[actix_rt::test]
+/// https://jsonapi.org/format/#crud-creating-client-ids
+async fn forbid_client_id_test() {
+    let mut app = init_app!("tests/config/actix.config.test.forbid_client_id.toml".to_string());
+
+    let dogs = generate_dogs(1);
+    let mut dog_resource =
+        ResourceDataWrapper::from_entities(&dogs, "http://localhost:1234/api/v1")
+            .pop()
+            .unwrap();
+    let req = post("/api/v1/dogs", &dog_resource);
+    let err: serde_json::Value = read_response_json(&mut app, req).await;
+    assert_eq!(err["errors"][0]["status"], "403");
+    assert_eq!(err["errors"][0]["code"], "RBH-0205");
+
+    dog_resource.data.id.id = String::new();
+    let req = post("/api/v1/dogs", &dog_resource);
+    let resp = call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+}
@@ -15,13 +15,35 @@ macro_rules! init_app {
     (DefaultPage) => {{
         init_app!("tests/config/actix.config.test.default_page.toml".to_string())
     }};
-    ($major:expr, $minor:expr) => {{
-        init_app!(format!(
-            "tests/config/actix.config.test.v{}_{}.toml",
-            $major, $minor
-        ))
+    (policy: $policy:expr) => {{
+        init_app!(
+            "tests/config/actix.config.test.v1_1.toml".to_string(),
+            policy: $policy
+        )
     }};
-    ($file_name:expr) => {{
+    (renderer: $renderer:expr) => {{
+        init_app!(
+            "tests/config/actix.config.test.v1_1.toml".to_string(),
+            policy: rabbithole::policy::AllowAll,
+            renderer: $renderer
+        )
+    }};
+    ($file_name:expr, policy: $policy:expr) => {{
+        init_app!(
+            $file_name,
+            policy: $policy,
+            renderer: rabbithole::render::DefaultRenderer
+        )
+    }};
+    (loaders: $loaders:expr) => {{
+        init_app!(
+            "tests/config/actix.config.test.v1_1.toml".to_string(),
+            policy: rabbithole::policy::AllowAll,
+            renderer: rabbithole::render::DefaultRenderer,
+            loaders: $loaders
+        )
+    }};
+    ($file_name:expr, policy: $policy:expr, renderer: $renderer:expr $(, loaders: $loaders:expr)?) => {{
         let mut settings = config::Config::default();
         settings
             .merge(config::File::with_name(&$file_name))
@@ -38,6 +60,13 @@ macro_rules! init_app {
                 .data(dog_service.clone())
                 .data(human_service.clone())
                 .data(actix_settings.clone())
+                .data::<std::sync::Arc<dyn rabbithole::policy::Policy>>(std::sync::Arc::new(
+                    $policy,
+                ))
+                .data::<std::sync::Arc<dyn rabbithole::render::ErrorRenderer>>(
+                    std::sync::Arc::new($renderer),
+                )
+                $( .data::<rabbithole::include::IncludeLoaders>($loaders) )?
                 .service(
                     actix_web::web::scope(&actix_settings.path)
                         .wrap(rabbithole_endpoint_actix::middleware::JsonApi)
@@ -52,6 +81,15 @@ macro_rules! init_app {
         )
         .await
     }};
+    ($major:expr, $minor:expr) => {{
+        init_app!(format!(
+            "tests/config/actix.config.test.v{}_{}.toml",
+            $major, $minor
+        ))
+    }};
+    ($file_name:expr) => {{
+        init_app!($file_name, policy: rabbithole::policy::AllowAll)
+    }};
 }
 
 use actix_web::test::TestRequest;
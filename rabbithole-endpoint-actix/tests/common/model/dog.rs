@@ -9,6 +9,7 @@ use uuid::Uuid;
 pub struct Dog {
     #[entity(id)]
     pub id: Uuid,
+    #[entity(validate(required, length(min = 1, max = 32)))]
     pub name: String,
 }
 
@@ -10,8 +10,10 @@ use uuid::Uuid;
 pub struct Human {
     #[entity(id)]
     pub id: Uuid,
+    #[entity(validate(required, regex = "^[0-9A-Za-z-]+$"))]
     pub name: String,
     #[entity(to_many)]
+    #[entity(validate(length(max = 8)))]
     pub dogs: Vec<Dog>,
 }
 
@@ -1,9 +1,10 @@
-use super::super::model::dog::Dog;
+use super::super::model::dog::{Dog, DogPatch};
 use super::super::service::*;
 use async_trait::async_trait;
 use futures::lock::Mutex;
+use rabbithole::entity::{EntityLookup, FromResource, Patchable, SingleEntity};
 use rabbithole::model::error;
-use rabbithole::model::resource::AttributeField;
+use rabbithole::model::resource::ResourceIdentifier;
 use rabbithole::operation::*;
 use rabbithole::query::Query;
 use std::collections::HashMap;
@@ -11,17 +12,17 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 #[derive(Default)]
-pub struct DogService(HashMap<String, Dog>);
+pub struct DogService(HashMap<Uuid, Dog>);
 impl DogService {
     pub fn new() -> Arc<Mutex<Self>> { Arc::new(Mutex::new(Default::default())) }
 
-    pub fn get_by_id(&self, id: &str) -> Option<Dog> { self.0.get(id).cloned() }
+    pub fn get_by_id(&self, id: &Uuid) -> Option<Dog> { self.0.get(id).cloned() }
 
     pub fn get_by_ids(&self, ids: &[String]) -> Result<Vec<Dog>, error::Error> {
         let res: Result<Vec<Dog>, error::Error> = ids
             .iter()
             .map(|id| {
-                if let Some(dog) = self.0.get(id) {
+                if let Some(dog) = Uuid::parse_str(id).ok().and_then(|id| self.0.get(&id)) {
                     Ok(dog.clone())
                 } else {
                     Err(INVALID_IDS_CONTAINED.clone())
@@ -55,7 +56,7 @@ impl Fetching for DogService {
 
     async fn fetch_single(
         &self,
-        id: &str,
+        id: &Uuid,
         _uri: &str,
         _path: &http::Uri,
         _query: &Query,
@@ -73,59 +74,51 @@ impl Creating for DogService {
         data: &ResourceDataWrapper,
         _uri: &str,
         _path: &http::Uri,
-    ) -> SingleResult<Dog> {
-        let ResourceDataWrapper { data } = data;
-        let id = if !data.id.id.is_empty() {
-            if self.0.contains_key(&data.id.id) {
-                Err(DUPLICATE_ID.clone())
-            } else {
-                Uuid::parse_str(&data.id.id).map_err(|_| INVALID_UUID.clone())
-            }
-        } else {
-            Ok(Uuid::new_v4())
-        }?;
-        if let AttributeField(serde_json::Value::String(name)) =
-            data.attributes.get_field("name")?
-        {
-            let dog = Dog {
-                id,
-                name: name.clone(),
-            };
-            self.0.insert(dog.id.clone().to_string(), dog.clone());
-            Ok(OperationResultData {
-                data: Some(dog),
-                ..Default::default()
-            })
-        } else {
-            Err(WRONG_FIELD_TYPE.clone())
+    ) -> CreateResult<Dog> {
+        let mut data = data.data.clone();
+        if data.id.id.is_empty() {
+            data.id.id = Uuid::new_v4().to_string();
+        }
+        let dog = Dog::from_resource(&data, &()).await?;
+        if self.0.contains_key(&dog.id) {
+            return Err(error::Error::DuplicateId(
+                &Dog::ty(),
+                &data.id.id,
+                Some(error::ErrorSource::from_pointer("/data/id")),
+            )
+            .into());
         }
+        self.0.insert(dog.id, dog.clone());
+        Ok(OperationResultData {
+            data: Created::Resource(dog),
+            ..Default::default()
+        })
+    }
+}
+#[async_trait]
+impl EntityLookup<Dog> for DogService {
+    async fn lookup_entities(&self, ids: &[ResourceIdentifier]) -> Result<Vec<Dog>, error::Error> {
+        let ids: Vec<String> = ids.iter().map(|id| id.id.clone()).collect();
+        self.get_by_ids(&ids)
     }
 }
 #[async_trait]
 impl Updating for DogService {
     async fn update_resource(
         &mut self,
-        id: &str,
-        data: &ResourceDataWrapper,
+        id: &Uuid,
+        patch: DogPatch,
         _uri: &str,
         _path: &http::Uri,
     ) -> SingleResult<Dog> {
-        if let Some(mut dog) = self.get_by_id(id) {
-            let ResourceDataWrapper { data } = data;
-            if let AttributeField(serde_json::Value::String(name)) =
-                data.attributes.get_field("name")?
-            {
-                dog.name = name.to_string();
-                self.0.insert(id.into(), dog);
-                Ok(OperationResultData {
-                    data: None,
-                    ..Default::default()
-                })
-            } else {
-                Err(WRONG_FIELD_TYPE.clone())
-            }
+        if let Some(dog) = self.0.get_mut(id) {
+            dog.apply_patch(patch);
+            Ok(OperationResultData {
+                data: None,
+                ..Default::default()
+            })
         } else {
-            Err(ENTITY_NOT_FOUND.clone())
+            Err(ENTITY_NOT_FOUND.clone().into())
         }
     }
 }
@@ -133,7 +126,7 @@ impl Updating for DogService {
 impl Deleting for DogService {
     async fn delete_resource(
         &mut self,
-        id: &str,
+        id: &Uuid,
         _uri: &str,
         _path: &http::Uri,
     ) -> OperationResult<()> {
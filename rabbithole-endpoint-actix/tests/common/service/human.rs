@@ -1,21 +1,19 @@
 use super::super::model::dog::Dog;
-use super::super::model::human::Human;
+use super::super::model::human::{Human, HumanPatch};
 use super::super::service::dog::DogService;
 use super::super::service::*;
 use async_trait::async_trait;
 use futures::lock::Mutex;
-use rabbithole::entity::{Entity, SingleEntity};
-use rabbithole::model::document::Document;
-use rabbithole::model::error::Error;
-use rabbithole::model::relationship::Relationship;
-use rabbithole::model::resource::{AttributeField, IdentifierData, ResourceIdentifier};
+use rabbithole::entity::{EntityLookup, FromResource, Patchable, SingleEntity};
+use rabbithole::model::error::{Error, ErrorSource};
+use rabbithole::model::resource::{IdentifierData, ResourceIdentifier};
 use rabbithole::operation::*;
 use rabbithole::query::Query;
 use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
-pub struct HumanService(HashMap<String, Human>, Arc<Mutex<DogService>>);
+pub struct HumanService(HashMap<Uuid, Human>, Arc<Mutex<DogService>>);
 impl HumanService {
     pub fn new(dog_service: Arc<Mutex<DogService>>) -> Arc<Mutex<HumanService>> {
         Arc::new(Mutex::new(Self(Default::default(), dog_service)))
@@ -41,135 +39,73 @@ impl Fetching for HumanService {
     }
 
     async fn fetch_single(
-        &self, id: &str, _uri: &str, _path: &http::Uri, _query: &Query,
+        &self, id: &Uuid, _uri: &str, _path: &http::Uri, _query: &Query,
     ) -> SingleResult<Human> {
         Ok(OperationResultData {
             data: self.0.get(id).map(Clone::clone),
             ..Default::default()
         })
     }
-
-    async fn fetch_relationship(
-        &self, id: &str, related_field: &str, uri: &str, _path: &http::Uri, query: &Query,
-    ) -> OperationResult<Relationship> {
-        if let Some(human) = self.0.get(id) {
-            let resource = human.to_resource(&uri.to_string(), &query.fields).unwrap();
-            if let Some(relat) = resource.relationships.get(related_field) {
-                Ok(OperationResultData {
-                    data: relat.clone(),
-                    ..Default::default()
-                })
-            } else {
-                Err(error::Error::FieldNotExist(related_field, None))
-            }
-        } else {
-            Err(ENTITY_NOT_FOUND.clone())
-        }
-    }
-
-    async fn fetch_related(
-        &self, id: &str, related_field: &str, uri: &str, path: &http::Uri, query: &Query,
-    ) -> Result<Document, Error> {
-        if let Some(human) = self.0.get(id) {
-            if related_field == "dogs" {
-                Ok(human.dogs.to_document(
-                    &uri.to_string(),
-                    query,
-                    path.clone(),
-                    Default::default(),
-                    Default::default(),
-                )?)
-            } else {
-                Err(error::Error::FieldNotExist(related_field, None))
-            }
-        } else {
-            Err(ENTITY_NOT_FOUND.clone())
-        }
-    }
 }
 
 #[async_trait]
 impl Creating for HumanService {
     async fn create(
         &mut self, data: &ResourceDataWrapper, _uri: &str, _path: &http::Uri,
-    ) -> SingleResult<Human> {
-        let ResourceDataWrapper { data } = data;
-        let id = if !data.id.id.is_empty() {
-            if self.0.contains_key(&data.id.id) {
-                Err(DUPLICATE_ID.clone())
-            } else {
-                Uuid::parse_str(&data.id.id).map_err(|_| INVALID_UUID.clone())
-            }
-        } else {
-            Ok(Uuid::new_v4())
-        }?;
-
-        let dog_ids: Vec<ResourceIdentifier> = data
-            .relationships
-            .get("dogs")
-            .map(|r| r.data.data())
-            .unwrap_or_default();
-        let dog_ids: Vec<String> = dog_ids.iter().map(|id| id.id.clone()).collect();
-        let dogs: Vec<Dog> = self.1.lock().await.get_by_ids(&dog_ids)?;
-
-        if let AttributeField(serde_json::Value::String(name)) =
-            data.attributes.get_field("name")?
-        {
-            let human = Human {
-                id,
-                name: name.clone(),
-                dogs,
-            };
-            self.0.insert(human.id.clone().to_string(), human.clone());
-            Ok(OperationResultData {
-                data: Some(human),
-                ..Default::default()
-            })
-        } else {
-            Err(WRONG_FIELD_TYPE.clone())
+    ) -> CreateResult<Human> {
+        let mut data = data.data.clone();
+        if data.id.id.is_empty() {
+            data.id.id = Uuid::new_v4().to_string();
         }
+        let human = Human::from_resource(&data, &*self.1.lock().await).await?;
+        if self.0.contains_key(&human.id) {
+            return Err(Error::DuplicateId(
+                &Human::ty(),
+                &data.id.id,
+                Some(ErrorSource::from_pointer("/data/id")),
+            )
+            .into());
+        }
+        self.0.insert(human.id, human.clone());
+        Ok(OperationResultData {
+            data: Created::Resource(human),
+            ..Default::default()
+        })
+    }
+}
+
+#[async_trait]
+impl EntityLookup<Dog> for HumanService {
+    async fn lookup_entities(&self, ids: &[ResourceIdentifier]) -> Result<Vec<Dog>, Error> {
+        EntityLookup::<Dog>::lookup_entities(&*self.1.lock().await, ids).await
     }
 }
 
 #[async_trait]
 impl Updating for HumanService {
     async fn update_resource(
-        &mut self, id: &str, data: &ResourceDataWrapper, _uri: &str, _path: &http::Uri,
+        &mut self, id: &Uuid, patch: HumanPatch, _uri: &str, _path: &http::Uri,
     ) -> SingleResult<Human> {
-        if let Some(mut human) = self.0.get(id).cloned() {
-            let new_attrs = &data.data.attributes;
-            let new_relats = &data.data.relationships;
-            if let Some(dog_ids) = new_relats.get("dogs").map(|r| r.data.data()) {
-                let dog_ids: Vec<String> = dog_ids
-                    .iter()
-                    .map(|r: &ResourceIdentifier| r.id.clone())
-                    .collect();
-                let dogs = self.1.clone().lock().await.get_by_ids(&dog_ids)?;
-                human.dogs = dogs;
-            }
-            if let Ok(AttributeField(serde_json::Value::String(name))) = new_attrs.get_field("name")
-            {
-                human.name = name.clone();
-            }
-            self.0.insert(id.to_string(), human);
+        if let Some(human) = self.0.get_mut(id) {
+            human.apply_patch(patch);
             Ok(OperationResultData {
                 data: None,
                 ..Default::default()
             })
         } else {
-            Err(ENTITY_NOT_FOUND.clone())
+            Err(ENTITY_NOT_FOUND.clone().into())
         }
     }
 
     async fn replace_relationship(
-        &mut self, id_field: &(String, String), data: &IdentifierDataWrapper, _uri: &str,
+        &mut self, id_field: &(Uuid, String), data: &IdentifierDataWrapper, _uri: &str,
         _path: &http::Uri,
     ) -> UpdateResult<Human> {
         let (id, field) = id_field;
         if let Some(human) = self.0.get_mut(id) {
             let IdentifierDataWrapper { data } = data;
             match data {
-                IdentifierData::Single(_) => Err(MULTIPLE_RELATIONSHIP_NEEDED.clone()),
+                IdentifierData::Single(_) => Err(MULTIPLE_RELATIONSHIP_NEEDED.clone().into()),
                 IdentifierData::Multiple(datas) => {
                     let ids: Vec<String> = datas
                         .iter()
@@ -190,19 +126,19 @@ impl Updating for HumanService {
                 },
             }
         } else {
-            Err(ENTITY_NOT_FOUND.clone())
+            Err(ENTITY_NOT_FOUND.clone().into())
         }
     }
 
     async fn add_relationship(
-        &mut self, id_field: &(String, String), data: &IdentifierDataWrapper, _uri: &str,
+        &mut self, id_field: &(Uuid, String), data: &IdentifierDataWrapper, _uri: &str,
         _path: &http::Uri,
     ) -> UpdateResult<Human> {
         let (id, field) = id_field;
         if let Some(human) = self.0.get_mut(id) {
             let IdentifierDataWrapper { data } = data;
             match data {
-                IdentifierData::Single(_) => Err(MULTIPLE_RELATIONSHIP_NEEDED.clone()),
+                IdentifierData::Single(_) => Err(MULTIPLE_RELATIONSHIP_NEEDED.clone().into()),
                 IdentifierData::Multiple(datas) => {
                     let ids: Vec<String> = datas
                         .iter()
@@ -223,19 +159,19 @@ impl Updating for HumanService {
                 },
             }
         } else {
-            Err(ENTITY_NOT_FOUND.clone())
+            Err(ENTITY_NOT_FOUND.clone().into())
         }
     }
 
     async fn remove_relationship(
-        &mut self, id_field: &(String, String), data: &IdentifierDataWrapper, _uri: &str,
+        &mut self, id_field: &(Uuid, String), data: &IdentifierDataWrapper, _uri: &str,
         _path: &http::Uri,
     ) -> UpdateResult<Human> {
         let (id, field) = id_field;
         if let Some(human) = self.0.get_mut(id) {
             let IdentifierDataWrapper { data } = data;
             match data {
-                IdentifierData::Single(_) => Err(MULTIPLE_RELATIONSHIP_NEEDED.clone()),
+                IdentifierData::Single(_) => Err(MULTIPLE_RELATIONSHIP_NEEDED.clone().into()),
                 IdentifierData::Multiple(datas) => {
                     let ids: Vec<String> = datas
                         .iter()
@@ -255,7 +191,7 @@ impl Updating for HumanService {
                 },
             }
         } else {
-            Err(ENTITY_NOT_FOUND.clone())
+            Err(ENTITY_NOT_FOUND.clone().into())
         }
     }
 }
@@ -263,7 +199,7 @@ impl Updating for HumanService {
 #[async_trait]
 impl Deleting for HumanService {
     async fn delete_resource(
-        &mut self, id: &str, _uri: &str, _path: &http::Uri,
+        &mut self, id: &Uuid, _uri: &str, _path: &http::Uri,
     ) -> OperationResult<()> {
         self.0.remove(id);
         Ok(OperationResultData {
@@ -33,16 +33,4 @@ lazy_static! {
         title: Some("Multiple Relationship Needed".into()),
         ..Default::default()
     };
-    pub static ref INVALID_UUID: error::Error = error::Error {
-        status: Some("400".into()),
-        code: Some("6".into()),
-        title: Some("Invalid UUID".into()),
-        ..Default::default()
-    };
-    pub static ref DUPLICATE_ID: error::Error = error::Error {
-        status: Some("400".into()),
-        code: Some("7".into()),
-        title: Some("Duplicate ID".into()),
-        ..Default::default()
-    };
 }
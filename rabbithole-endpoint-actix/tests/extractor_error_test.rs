@@ -0,0 +1,80 @@
+#[macro_use]
+extern crate lazy_static;
+
+pub mod common;
+
+use actix_web::http::{header, StatusCode};
+use actix_web::test::{call_service, read_response_json, TestRequest};
+use common::request;
+use common::service;
+use rabbithole_endpoint_actix::ActixSettings;
+
+#[actix_rt::test]
+async fn invalid_body_test() {
+    let mut app = init_app!(1, 1);
+
+    for payload in &["not a json", r#"{"meta": {}}"#] {
+        let req = request(TestRequest::post(), "/api/v1/dogs")
+            .set_payload(payload.to_string())
+            .to_request();
+        let resp = call_service(&mut app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let req = request(TestRequest::post(), "/api/v1/dogs")
+            .set_payload(payload.to_string())
+            .to_request();
+        let doc: serde_json::Value = read_response_json(&mut app, req).await;
+        assert_eq!(doc["errors"][0]["code"], "RBH-0003");
+    }
+}
+
+#[actix_rt::test]
+async fn route_test() {
+    let mut app = init_app!(1, 1);
+
+    let req = request(TestRequest::put(), "/api/v1/dogs").to_request();
+    let resp = call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(resp.headers().get(header::ALLOW).unwrap(), "GET, POST");
+    let req = request(TestRequest::put(), "/api/v1/dogs/1").to_request();
+    let doc: serde_json::Value = read_response_json(&mut app, req).await;
+    assert_eq!(doc["errors"][0]["code"], "RBH-0009");
+
+    let req = request(
+        TestRequest::get(),
+        "/api/v1/dogs/1/relationships/master/more",
+    )
+    .to_request();
+    let resp = call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let req = request(
+        TestRequest::get(),
+        "/api/v1/dogs/1/relationships/master/more",
+    )
+    .to_request();
+    let doc: serde_json::Value = read_response_json(&mut app, req).await;
+    assert_eq!(doc["errors"][0]["code"], "RBH-0008");
+    assert_eq!(
+        doc["errors"][0]["links"]["about"],
+        "https://docs.example/errors/RBH-0008"
+    );
+}
+
+#[actix_rt::test]
+async fn invalid_id_test() {
+    let mut app = init_app!(1, 1);
+
+    let req = request(TestRequest::get(), "/api/v1/dogs/1").to_request();
+    let resp = call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    for path in &["/api/v1/dogs/1", "/api/v1/people/1/dogs"] {
+        let req = request(TestRequest::get(), path).to_request();
+        let doc: serde_json::Value = read_response_json(&mut app, req).await;
+        assert_eq!(doc["errors"][0]["code"], "RBH-0007");
+        assert!(doc["errors"][0]["detail"]
+            .as_str()
+            .unwrap()
+            .contains("invalid id `1`"));
+    }
+}
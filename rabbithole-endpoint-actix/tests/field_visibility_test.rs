@@ -0,0 +1,118 @@
+#[macro_use]
+extern crate lazy_static;
+
+pub mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test::{call_service, read_response_json, TestRequest};
+use common::model::dog::generate_dogs;
+use common::model::human::Human;
+use common::service;
+use common::{get, post, request};
+use rabbithole::model::document::Document;
+use rabbithole::operation::ResourceDataWrapper;
+use rabbithole::policy::{FieldVisibility, Policy, PolicyContext};
+use rabbithole_endpoint_actix::ActixSettings;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Only the admins can see the dogs of the people, and rename the dogs
+struct RolePolicy;
+
+impl Policy for RolePolicy {
+    fn field_visibility(&self, ctx: &PolicyContext) -> Option<Arc<dyn FieldVisibility>> {
+        match ctx.headers.get("x-role") {
+            Some(role) if role == "admin" => None,
+            _ => Some(Arc::new(Guest)),
+        }
+    }
+}
+
+struct Guest;
+
+impl FieldVisibility for Guest {
+    fn can_read_field(&self, ty: &str, field: &str) -> bool { !(ty == "people" && field == "dogs") }
+
+    fn can_write_field(&self, ty: &str, field: &str) -> bool {
+        self.can_read_field(ty, field) && !(ty == "dogs" && field == "name")
+    }
+}
+
+fn admin_post<D: Serialize>(uri: &str, data: &D) -> actix_http::Request {
+    request(TestRequest::post(), uri)
+        .header("x-role", "admin")
+        .set_payload(serde_json::to_string(data).unwrap())
+        .to_request()
+}
+
+fn admin_get(uri: &str) -> actix_http::Request {
+    request(TestRequest::get(), uri)
+        .header("x-role", "admin")
+        .to_request()
+}
+
+#[actix_rt::test]
+async fn field_visibility_test() {
+    let mut app = init_app!(policy: RolePolicy);
+
+    let dogs = generate_dogs(2);
+    let dog_resources = ResourceDataWrapper::from_entities(&dogs, "http://localhost:1234/api/v1");
+
+    // Guests cannot set the name of a dog
+    let req = post("/api/v1/dogs", &dog_resources[0]);
+    let resp = call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    let err: serde_json::Value =
+        read_response_json(&mut app, post("/api/v1/dogs", &dog_resources[0])).await;
+    assert_eq!(err["errors"][0]["code"], "RBH-0202");
+    assert_eq!(err["errors"][0]["source"]["pointer"], "/data/attributes/name");
+
+    // While admins can
+    for dog in &dog_resources {
+        let resp = call_service(&mut app, admin_post("/api/v1/dogs", dog)).await;
+        assert!(resp.status().is_success());
+    }
+
+    let human: Human = dogs.as_slice().into();
+    let human_resource = ResourceDataWrapper::from_entities(
+        std::slice::from_ref(&human),
+        "http://localhost:1234/api/v1",
+    );
+    let req = post("/api/v1/people", &human_resource[0]);
+    let resp = call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    let resp = call_service(&mut app, admin_post("/api/v1/people", &human_resource[0])).await;
+    assert!(resp.status().is_success());
+
+    // The dogs of the human are hidden from the guests
+    let uri = format!("/api/v1/people/{}", human.id);
+    let doc: Document = read_response_json(&mut app, get(&uri)).await;
+    let (resource, included) = doc.into_single().unwrap();
+    assert!(!resource.relationships.contains_key("dogs"));
+    assert!(resource.attributes.get_field("name").is_ok());
+    assert!(included.is_empty());
+
+    for path in &["relationships/dogs", "dogs"] {
+        let req = get(&format!("{}/{}", uri, path));
+        let resp = call_service(&mut app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let req = get(&format!("{}/{}", uri, path));
+        let err: serde_json::Value = read_response_json(&mut app, req).await;
+        assert_eq!(err["errors"][0]["code"], "RBH-0401");
+    }
+
+    let doc: Document = read_response_json(&mut app, admin_get(&uri)).await;
+    let (resource, _) = doc.into_single().unwrap();
+    assert_eq!(
+        resource
+            .relationships
+            .get("dogs")
+            .unwrap()
+            .data
+            .as_ref()
+            .unwrap()
+            .data()
+            .len(),
+        2
+    );
+}
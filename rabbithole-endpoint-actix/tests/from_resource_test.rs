@@ -0,0 +1,92 @@
+#[macro_use]
+extern crate lazy_static;
+
+pub mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test::{call_service, read_response_json};
+use common::model::dog::generate_dogs;
+use common::model::human::Human;
+use common::post;
+use common::service;
+use rabbithole::model::document::Document;
+use rabbithole::model::resource::{IdentifierData, ResourceIdentifier};
+use rabbithole::operation::ResourceDataWrapper;
+use rabbithole_endpoint_actix::ActixSettings;
+use serde_json::json;
+use std::collections::HashMap;
+use std::iter::FromIterator;
+
+#[actix_rt::test]
+async fn from_resource_test() {
+    let mut app = init_app!(1, 1);
+
+    let dogs = generate_dogs(2);
+    let dog_resources = ResourceDataWrapper::from_entities(&dogs, "http://localhost:1234/api/v1");
+
+    // An attribute of a wrong type
+    let mut dog = dog_resources[0].clone();
+    let attrs: HashMap<String, serde_json::Value> =
+        HashMap::from_iter(vec![("name".to_string(), json!(42))]);
+    dog.data.attributes = attrs.into();
+    let err: serde_json::Value = read_response_json(&mut app, post("/api/v1/dogs", &dog)).await;
+    assert_eq!(err["errors"][0]["code"], "RBH-0209");
+    assert_eq!(err["errors"][0]["source"]["pointer"], "/data/attributes/name");
+
+    // A missing attribute, which is required by the validation of `Dog`
+    dog.data.attributes = Default::default();
+    let doc: serde_json::Value = read_response_json(&mut app, post("/api/v1/dogs", &dog)).await;
+    assert_eq!(doc["errors"][0]["code"], "RBH-0210");
+    assert_eq!(doc["errors"][0]["source"]["pointer"], "/data/attributes/name");
+
+    // A malformed id
+    let mut dog = dog_resources[0].clone();
+    dog.data.id.id = "not-a-uuid".into();
+    let err: serde_json::Value = read_response_json(&mut app, post("/api/v1/dogs", &dog)).await;
+    assert_eq!(err["errors"][0]["code"], "RBH-0209");
+    assert_eq!(err["errors"][0]["source"]["pointer"], "/data/id");
+
+    // The id is generated by the service if missing
+    let mut dog = dog_resources[0].clone();
+    dog.data.id.id = String::new();
+    let doc: Document = read_response_json(&mut app, post("/api/v1/dogs", &dog)).await;
+    let (resource, _) = doc.into_single().unwrap();
+    assert!(!resource.id.id.is_empty());
+    assert_eq!(resource.attributes, dog.data.attributes);
+
+    for dog in &dog_resources {
+        let resp = call_service(&mut app, post("/api/v1/dogs", dog)).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
+
+    // The related dogs are resolved by the `DogService`
+    let human: Human = dogs.as_slice().into();
+    let mut human_resource = ResourceDataWrapper::from_entities(
+        std::slice::from_ref(&human),
+        "http://localhost:1234/api/v1",
+    )
+    .pop()
+    .unwrap();
+    let doc: Document = read_response_json(
+        &mut app,
+        post("/api/v1/people?include=dogs", &human_resource),
+    )
+    .await;
+    let (resource, included) = doc.into_single().unwrap();
+    assert_eq!(resource.relationships, human_resource.data.relationships);
+    assert_eq!(included.len(), 2);
+
+    // Which fails when any of them does not exist
+    human_resource.data.id.id = String::new();
+    human_resource
+        .data
+        .relationships
+        .get_mut("dogs")
+        .unwrap()
+        .data = Some(IdentifierData::Multiple(vec![ResourceIdentifier::new(
+        "dogs",
+        "not-exist",
+    )]));
+    let resp = call_service(&mut app, post("/api/v1/people", &human_resource)).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
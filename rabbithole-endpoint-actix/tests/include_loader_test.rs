@@ -0,0 +1,242 @@
+#[macro_use]
+extern crate lazy_static;
+extern crate rabbithole_derive as rbh_derive;
+
+pub mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test::{call_service, init_service, read_response_json};
+use common::{get, post};
+use futures::lock::Mutex;
+use rabbithole::entity::{EntityLookup, FromResource, SingleEntity};
+use rabbithole::include::{IncludeLoader, IncludeLoaders};
+use rabbithole::model::document::Document;
+use rabbithole::model::error::Error;
+use rabbithole::model::resource::{Resource, ResourceIdentifier};
+use rabbithole::operation::*;
+use rabbithole::query::Query;
+use rabbithole_endpoint_actix::ActixSettings;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// The friends of a wolf hold only their ids, so they are included through the `IncludeLoaders`
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone, Debug)]
+#[entity(type = "wolves")]
+#[entity(service(WolfService))]
+#[entity(backend(actix))]
+pub struct Wolf {
+    #[entity(id)]
+    pub id: Uuid,
+    pub name: String,
+    #[entity(to_many(type = "wolves"))]
+    pub friends: Vec<Uuid>,
+}
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone, Debug)]
+#[entity(type = "packs")]
+#[entity(service(PackService))]
+#[entity(backend(actix))]
+pub struct Pack {
+    #[entity(id)]
+    pub id: Uuid,
+    #[entity(to_many)]
+    pub wolves: Vec<Wolf>,
+}
+
+pub struct WolfService(HashMap<Uuid, Wolf>);
+
+impl Operation for WolfService {
+    type Item = Wolf;
+}
+
+#[rabbithole::async_trait]
+impl Fetching for WolfService {
+    async fn fetch_single(
+        &self, id: &Uuid, _uri: &str, _path: &http::Uri, _query: &Query,
+    ) -> SingleResult<Wolf> {
+        Ok(OperationResultData {
+            data: self.0.get(id).cloned(),
+            ..Default::default()
+        })
+    }
+}
+
+impl Creating for WolfService {}
+impl Updating for WolfService {}
+impl Deleting for WolfService {}
+
+pub struct PackService(HashMap<Uuid, Pack>, Arc<Mutex<WolfService>>);
+
+impl Operation for PackService {
+    type Item = Pack;
+}
+
+#[rabbithole::async_trait]
+impl Fetching for PackService {
+    async fn fetch_single(
+        &self, id: &Uuid, _uri: &str, _path: &http::Uri, _query: &Query,
+    ) -> SingleResult<Pack> {
+        Ok(OperationResultData {
+            data: self.0.get(id).cloned(),
+            ..Default::default()
+        })
+    }
+}
+
+#[rabbithole::async_trait]
+impl Creating for PackService {
+    async fn create(
+        &mut self, data: &ResourceDataWrapper, _uri: &str, _path: &http::Uri,
+    ) -> CreateResult<Pack> {
+        let pack = Pack::from_resource(&data.data, &*self).await?;
+        self.0.insert(pack.id, pack.clone());
+        Ok(OperationResultData {
+            data: Created::Resource(pack),
+            ..Default::default()
+        })
+    }
+}
+
+#[rabbithole::async_trait]
+impl EntityLookup<Wolf> for PackService {
+    async fn lookup_entities(&self, ids: &[ResourceIdentifier]) -> rabbithole::Result<Vec<Wolf>> {
+        let wolves = self.1.lock().await;
+        ids.iter()
+            .map(|id| {
+                Uuid::parse_str(&id.id)
+                    .ok()
+                    .and_then(|uuid| wolves.0.get(&uuid).cloned())
+                    .ok_or_else(|| Error::ResourceNotExist(&id.id, None))
+            })
+            .collect()
+    }
+}
+
+impl Updating for PackService {}
+impl Deleting for PackService {}
+
+/// Loads the wolves out of `wolves`, counting the calls
+#[derive(Clone)]
+struct WolfLoader {
+    wolves: Vec<Wolf>,
+    calls: Arc<AtomicUsize>,
+}
+
+#[rabbithole::async_trait]
+impl IncludeLoader for WolfLoader {
+    async fn load(
+        &self, ids: &[ResourceIdentifier], uri: &str,
+    ) -> rabbithole::Result<Vec<Resource>> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(self
+            .wolves
+            .iter()
+            .filter(|wolf| ids.iter().any(|id| id.id == wolf.id.to_string()))
+            .filter_map(|wolf| {
+                wolf.to_resource(uri, &Default::default(), &rabbithole::policy::AllowAll)
+            })
+            .collect())
+    }
+}
+
+fn generate_wolves(len: usize) -> Vec<Wolf> {
+    (0 .. len)
+        .map(|i| Wolf {
+            id: Uuid::new_v4(),
+            name: i.to_string(),
+            friends: vec![],
+        })
+        .collect()
+}
+
+#[actix_rt::test]
+async fn nested_include_test() {
+    // The wolves of the pack are both friends of the third one, who is a friend of the fourth
+    let mut wolves = generate_wolves(4);
+    wolves[2].friends = vec![wolves[3].id];
+    wolves[0].friends = vec![wolves[2].id];
+    wolves[1].friends = vec![wolves[2].id];
+    let pack = Pack {
+        id: Uuid::new_v4(),
+        wolves: wolves[.. 2].to_vec(),
+    };
+    let loader = WolfLoader {
+        wolves: wolves.clone(),
+        calls: Default::default(),
+    };
+    let uri = format!(
+        "/api/v1/packs/{}?include=wolves.friends.friends&fields[wolves]=name",
+        pack.id
+    );
+    let pack_resource = ResourceDataWrapper::from_entities(
+        std::slice::from_ref(&pack),
+        "http://localhost:1234/api/v1",
+    )
+    .remove(0);
+
+    let mut settings = config::Config::default();
+    settings
+        .merge(config::File::with_name(
+            "tests/config/actix.config.test.v1_1.toml",
+        ))
+        .unwrap();
+    let actix_settings: ActixSettings = settings.try_into().unwrap();
+
+    for with_loaders in &[false, true] {
+        let wolf_service = Arc::new(Mutex::new(WolfService(
+            wolves.iter().map(|wolf| (wolf.id, wolf.clone())).collect(),
+        )));
+        let pack_service = Arc::new(Mutex::new(PackService(
+            Default::default(),
+            wolf_service.clone(),
+        )));
+        let mut app = actix_web::App::new()
+            .data(wolf_service)
+            .data(pack_service)
+            .data(actix_settings.clone())
+            .data::<Arc<dyn rabbithole::policy::Policy>>(Arc::new(rabbithole::policy::AllowAll));
+        if *with_loaders {
+            app = app.data(IncludeLoaders::default().register("wolves", loader.clone()));
+        }
+        let mut app = init_service(
+            app.service(
+                actix_web::web::scope(&actix_settings.path)
+                    .wrap(rabbithole_endpoint_actix::middleware::JsonApi)
+                    .service(WolfService::actix_service())
+                    .service(PackService::actix_service()),
+            ),
+        )
+        .await;
+
+        if *with_loaders {
+            // The wolves of the pack are included from the memory, and the others are loaded
+            // once per level
+            let req = post("/api/v1/packs?include=wolves.friends", &pack_resource);
+            let doc: Document = read_response_json(&mut app, req).await;
+            let (_, included) = doc.into_single().unwrap();
+            assert_eq!(included.len(), 3);
+            assert_eq!(loader.calls.swap(0, Ordering::SeqCst), 1);
+
+            // Even if the sparse fieldsets leave out the `friends` on the paths
+            let doc: Document = read_response_json(&mut app, get(&uri)).await;
+            let (_, included) = doc.into_single().unwrap();
+            assert_eq!(included.len(), 4);
+            assert!(included.values().all(|wolf| wolf.relationships.is_empty()));
+            assert_eq!(loader.calls.swap(0, Ordering::SeqCst), 2);
+
+            let req = get(format!("/api/v1/packs/{}/wolves?include=friends", pack.id).as_str());
+            let doc: Document = read_response_json(&mut app, req).await;
+            let (_, included) = doc.into_multiple().unwrap();
+            assert_eq!(included.len(), 1);
+            assert_eq!(loader.calls.swap(0, Ordering::SeqCst), 1);
+        } else {
+            let resp = call_service(&mut app, post("/api/v1/packs", &pack_resource)).await;
+            assert_eq!(resp.status(), StatusCode::CREATED);
+            let doc: serde_json::Value = read_response_json(&mut app, get(&uri)).await;
+            assert_eq!(doc["errors"][0]["code"], "RBH-0004");
+        }
+    }
+}
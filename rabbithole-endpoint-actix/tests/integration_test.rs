@@ -33,16 +33,39 @@ async fn basic_crud_test() {
     for dog in dog_resources.clone() {
         let req = post("/api/v1/dogs", &dog);
         let resp = call_service(&mut app, req).await;
-        assert!(resp.status().is_success());
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        assert_eq!(
+            resp.headers().get("location").unwrap(),
+            &format!("http://localhost:1234/api/v1/dogs/{}", dog.data.id.id)
+        );
     }
 
+    // The created resource is wrapped in a top-level document
+    let dog = ResourceDataWrapper::from_entities(&generate_dogs(1), "http://localhost:1234/api/v1")
+        .pop()
+        .unwrap();
+    let req = post("/api/v1/dogs", &dog);
+    let doc: Document = read_response_json(&mut app, req).await;
+    assert_eq!(doc.links.get("self"), dog.data.links.get("self"));
+    assert_eq!(doc.jsonapi.unwrap().version.unwrap().to_string(), "1.0");
+    let req = delete(
+        format!("/api/v1/dogs/{}", dog.data.id.id).as_str(),
+        &json!({}),
+    );
+    let resp = call_service(&mut app, req).await;
+    assert!(resp.status().is_success());
+
     // Verify that 5 dogs created
     let req = get("/api/v1/dogs");
     let resp: Document = read_response_json(&mut app, req).await;
+    assert_eq!(
+        resp.jsonapi.clone().unwrap().version.unwrap().to_string(),
+        "1.0"
+    );
     assert_eq!(resp.into_multiple().unwrap().0.len(), 5);
 
     // Verify that data is null on possible relationship
-    let req = get("/api/v1/people/1");
+    let req = get(&format!("/api/v1/people/{}", uuid::Uuid::nil()));
     let resp: Document = read_response_json(&mut app, req).await;
     assert_eq!(resp.item, DocumentItem::PrimaryData(None));
 
@@ -107,14 +130,15 @@ async fn basic_crud_test() {
     let human_resources =
         ResourceDataWrapper::from_entities(&humans, "http://localhost:1234/api/v1");
     for human in &human_resources {
-        let req = post("/api/v1/people", &human);
+        let req = post("/api/v1/people?include=dogs", &human);
         let doc: Document = read_response_json(&mut app, req).await;
-        let (resource, _) = doc.into_single().unwrap();
+        let (resource, included) = doc.into_single().unwrap();
         assert_eq!(resource.id, human.data.id);
+        assert_eq!(included.len(), 2);
         assert!(resource.relationships.contains_key("dogs"));
 
         let dogs_relat = resource.relationships.get("dogs").unwrap();
-        let dogs_relat = dogs_relat.data.data();
+        let dogs_relat = dogs_relat.data.as_ref().unwrap().data();
         assert_eq!(dogs_relat.len(), 2);
     }
 
@@ -192,6 +216,8 @@ async fn relationship_test() {
             .get("dogs")
             .unwrap()
             .data
+            .as_ref()
+            .unwrap()
             .data()
             .is_empty()
     );
@@ -215,7 +241,15 @@ async fn relationship_test() {
         .get("dogs")
         .unwrap()
         .data
+        .as_ref()
+        .unwrap()
         .data();
     let first_pets_set: HashSet<ResourceIdentifier> = HashSet::from_iter(first_pets);
     assert_eq!(first_pets_set, HashSet::from_iter(dogs_idents));
+
+    // The relationship documents have the same top-level `jsonapi` as the others
+    let req = get(format!("/api/v1/people/{}/relationships/dogs", first_master_id).as_str());
+    let doc: serde_json::Value = read_response_json(&mut app, req).await;
+    assert_eq!(doc["jsonapi"]["version"], "1.0");
+    assert_eq!(doc["data"].as_array().unwrap().len(), 3);
 }
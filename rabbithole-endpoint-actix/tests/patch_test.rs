@@ -0,0 +1,113 @@
+#[macro_use]
+extern crate lazy_static;
+
+pub mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test::{call_service, read_response_json};
+use common::model::dog::generate_dogs;
+use common::model::human::Human;
+use common::service;
+use common::{get, patch, post};
+use rabbithole::model::document::Document;
+use rabbithole::model::resource::{IdentifierData, ResourceIdentifier};
+use rabbithole::operation::ResourceDataWrapper;
+use rabbithole_endpoint_actix::ActixSettings;
+use serde_json::json;
+use std::collections::HashMap;
+use std::iter::FromIterator;
+
+#[actix_rt::test]
+/// https://jsonapi.org/format/#crud-updating-resource-attributes
+async fn partial_update_test() {
+    let mut app = init_app!(1, 1);
+
+    let dogs = generate_dogs(2);
+    let dog_resources = ResourceDataWrapper::from_entities(&dogs, "http://localhost:1234/api/v1");
+    for dog in &dog_resources {
+        let resp = call_service(&mut app, post("/api/v1/dogs", dog)).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
+
+    let human: Human = dogs.as_slice().into();
+    let human_resource = ResourceDataWrapper::from_entities(
+        std::slice::from_ref(&human),
+        "http://localhost:1234/api/v1",
+    )
+    .pop()
+    .unwrap();
+    let resp = call_service(&mut app, post("/api/v1/people", &human_resource)).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let uri = format!("/api/v1/people/{}", human.id);
+
+    // Only the name is updated, and the dogs are left untouched
+    let mut name_only = human_resource.clone();
+    let attrs: HashMap<String, serde_json::Value> =
+        HashMap::from_iter(vec![("name".to_string(), json!("Alice"))]);
+    name_only.data.attributes = attrs.into();
+    name_only.data.relationships = Default::default();
+    let resp = call_service(&mut app, patch(&uri, &name_only)).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let doc: Document = read_response_json(&mut app, get(&uri)).await;
+    let (resource, _) = doc.into_single().unwrap();
+    assert_eq!(
+        resource.attributes.get_field("name").unwrap().0,
+        json!("Alice")
+    );
+    assert_eq!(
+        resource
+            .relationships
+            .get("dogs")
+            .unwrap()
+            .data
+            .as_ref()
+            .unwrap()
+            .data()
+            .len(),
+        2
+    );
+
+    // Only the dogs are updated, and the name is left untouched
+    let mut dogs_only = human_resource.clone();
+    dogs_only.data.attributes = Default::default();
+    dogs_only.data.relationships.get_mut("dogs").unwrap().data = Some(IdentifierData::Multiple(
+        vec![ResourceIdentifier::new("dogs", &dogs[0].id.to_string())],
+    ));
+    let resp = call_service(&mut app, patch(&uri, &dogs_only)).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let doc: Document = read_response_json(&mut app, get(&uri)).await;
+    let (resource, _) = doc.into_single().unwrap();
+    assert_eq!(
+        resource.attributes.get_field("name").unwrap().0,
+        json!("Alice")
+    );
+    assert_eq!(
+        resource
+            .relationships
+            .get("dogs")
+            .unwrap()
+            .data
+            .as_ref()
+            .unwrap()
+            .data()
+            .len(),
+        1
+    );
+
+    // The related dogs of the patch are resolved as well
+    dogs_only.data.relationships.get_mut("dogs").unwrap().data = Some(IdentifierData::Multiple(
+        vec![ResourceIdentifier::new("dogs", "not-exist")],
+    ));
+    let resp = call_service(&mut app, patch(&uri, &dogs_only)).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // The present attributes are still checked
+    let attrs: HashMap<String, serde_json::Value> =
+        HashMap::from_iter(vec![("name".to_string(), json!(42))]);
+    name_only.data.attributes = attrs.into();
+    let err: serde_json::Value = read_response_json(&mut app, patch(&uri, &name_only)).await;
+    assert_eq!(err["errors"][0]["code"], "RBH-0209");
+    assert_eq!(err["errors"][0]["source"]["pointer"], "/data/attributes/name");
+}
@@ -0,0 +1,147 @@
+#[macro_use]
+extern crate lazy_static;
+
+pub mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test::{call_service, read_response_json};
+use async_trait::async_trait;
+use common::model::dog::generate_dogs;
+use common::model::human::Human;
+use common::service;
+use common::{delete, get, post};
+use rabbithole::model::document::{Document, DocumentItem};
+use rabbithole::model::resource::{Resource, ResourceIdentifier};
+use rabbithole::operation::ResourceDataWrapper;
+use rabbithole::policy::{Action, Denial, Policy, PolicyContext};
+use rabbithole_endpoint_actix::ActixSettings;
+use serde_json::json;
+
+/// Hides the dogs named `hidden` and forbids deleting any dog
+struct DogPolicy;
+
+#[async_trait]
+impl Policy for DogPolicy {
+    async fn authorize(&self, ctx: &PolicyContext) -> Result<(), Denial> {
+        if ctx.ty == "dogs" && ctx.action == Action::DeleteResource {
+            Err(Denial::Forbidden)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn can_read(&self, _ctx: &PolicyContext, resource: &Resource) -> Result<(), Denial> {
+        match resource.attributes.get_field("name") {
+            Ok(name) if name.0 == json!("hidden") => Err(Denial::NotFound),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Hides the dogs of the ids, which are only known by their identifiers
+struct HiddenIds(Vec<String>);
+
+impl Policy for HiddenIds {
+    fn can_read(&self, ctx: &PolicyContext, resource: &Resource) -> Result<(), Denial> {
+        self.can_read_identifier(ctx, &resource.id)
+    }
+
+    fn can_read_identifier(
+        &self, _ctx: &PolicyContext, id: &ResourceIdentifier,
+    ) -> Result<(), Denial> {
+        if self.0.contains(&id.id) {
+            Err(Denial::NotFound)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[actix_rt::test]
+async fn policy_test() {
+    let mut app = init_app!(policy: DogPolicy);
+
+    let mut dogs = generate_dogs(3);
+    dogs[0].name = "hidden".into();
+    let dog_resources = ResourceDataWrapper::from_entities(&dogs, "http://localhost:1234/api/v1");
+    for dog in &dog_resources {
+        let req = post("/api/v1/dogs", dog);
+        let resp = call_service(&mut app, req).await;
+        assert!(resp.status().is_success());
+    }
+
+    // The hidden dog is filtered out of the collection
+    let req = get("/api/v1/dogs");
+    let doc: Document = read_response_json(&mut app, req).await;
+    assert_eq!(doc.into_multiple().unwrap().0.len(), 2);
+
+    // And cannot be fetched directly
+    let hidden_id = dogs[0].id.to_string();
+    let req = get(format!("/api/v1/dogs/{}", hidden_id).as_str());
+    let resp = call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let req = get(format!("/api/v1/dogs/{}", dogs[1].id).as_str());
+    let resp = call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Deleting is forbidden
+    let req = delete(format!("/api/v1/dogs/{}", dogs[1].id).as_str(), &json!({}));
+    let resp = call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let req = get("/api/v1/dogs");
+    let doc: Document = read_response_json(&mut app, req).await;
+    assert!(matches!(doc.item, DocumentItem::PrimaryData(Some(_))));
+    assert_eq!(doc.into_multiple().unwrap().0.len(), 2);
+}
+
+#[actix_rt::test]
+async fn policy_paging_test() {
+    let mut app = init_app!(policy: DogPolicy);
+
+    let mut dogs = generate_dogs(3);
+    dogs[0].name = "hidden".into();
+    let dog_resources = ResourceDataWrapper::from_entities(&dogs, "http://localhost:1234/api/v1");
+    for dog in &dog_resources {
+        let req = post("/api/v1/dogs", dog);
+        let resp = call_service(&mut app, req).await;
+        assert!(resp.status().is_success());
+    }
+
+    // The hidden dog is dropped before paging, so the first page is still full
+    let req = get("/api/v1/dogs?sort=-name&page[number]=0&page[size]=2");
+    let doc: Document = read_response_json(&mut app, req).await;
+    let (dogs_page, _) = doc.into_multiple().unwrap();
+    assert_eq!(dogs_page.len(), 2);
+}
+
+#[actix_rt::test]
+async fn policy_linkage_test() {
+    let dogs = generate_dogs(3);
+    let human = Human::from(&[dogs[0].clone(), dogs[2].clone()][..]);
+    let mut app = init_app!(policy: HiddenIds(vec![dogs[0].id.to_string()]));
+
+    let dog_resources = ResourceDataWrapper::from_entities(&dogs, "http://localhost:1234/api/v1");
+    for dog in &dog_resources {
+        let req = post("/api/v1/dogs", dog);
+        let resp = call_service(&mut app, req).await;
+        assert!(resp.status().is_success());
+    }
+    let human_resource = ResourceDataWrapper::from_entities(
+        std::slice::from_ref(&human),
+        "http://localhost:1234/api/v1",
+    );
+    let resp = call_service(&mut app, post("/api/v1/people", &human_resource[0])).await;
+    assert!(resp.status().is_success());
+
+    let visible = json!([{ "type": "dogs", "id": dogs[2].id.to_string() }]);
+
+    let req = get(format!("/api/v1/people/{}", human.id).as_str());
+    let doc: serde_json::Value = read_response_json(&mut app, req).await;
+    assert_eq!(doc["data"]["relationships"]["dogs"]["data"], visible);
+
+    let req = get(format!("/api/v1/people/{}/relationships/dogs", human.id).as_str());
+    let relat: serde_json::Value = read_response_json(&mut app, req).await;
+    assert_eq!(relat["data"], visible);
+}
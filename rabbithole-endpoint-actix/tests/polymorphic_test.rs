@@ -0,0 +1,94 @@
+#[macro_use]
+extern crate lazy_static;
+extern crate rabbithole_derive as rbh_derive;
+
+pub mod common;
+
+use actix_web::http::{header, StatusCode};
+use actix_web::test::{call_service, init_service, read_response_json};
+use common::model::dog::Dog;
+use common::{get, patch};
+use futures::lock::Mutex;
+use rabbithole::entity::SingleEntity;
+use rabbithole::operation::*;
+use rabbithole::query::Query;
+use rabbithole_endpoint_actix::ActixSettings;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone, Debug)]
+#[entity(type = "pets")]
+#[entity(service(PetService))]
+#[entity(backend(actix))]
+pub enum Pet {
+    Dog(Dog),
+}
+
+pub struct PetService(Vec<Pet>);
+
+impl Operation for PetService {
+    type Item = Pet;
+}
+
+#[rabbithole::async_trait]
+impl Fetching for PetService {
+    async fn fetch_collection(
+        &self, uri: &str, path: &http::Uri, query: &Query,
+    ) -> CollectionResult<Pet> {
+        let (data, links) = query.query(self.0.clone(), uri, path)?;
+        Ok(OperationResultData {
+            data,
+            additional_links: links,
+            ..Default::default()
+        })
+    }
+
+    async fn fetch_single(
+        &self, id: &String, _uri: &str, _path: &http::Uri, _query: &Query,
+    ) -> SingleResult<Pet> {
+        Ok(OperationResultData {
+            data: self.0.iter().find(|pet| &pet.id() == id).cloned(),
+            ..Default::default()
+        })
+    }
+}
+
+impl Creating for PetService {}
+impl Updating for PetService {}
+impl Deleting for PetService {}
+
+#[actix_rt::test]
+async fn enum_backend_test() {
+    let mut settings = config::Config::default();
+    settings
+        .merge(config::File::with_name(
+            "tests/config/actix.config.test.v1_1.toml",
+        ))
+        .unwrap();
+    let actix_settings: ActixSettings = settings.try_into().unwrap();
+    let dog = common::model::dog::generate_dogs(1).pop().unwrap();
+    let pets = Arc::new(Mutex::new(PetService(vec![Pet::Dog(dog.clone())])));
+
+    let mut app = init_service(
+        actix_web::App::new()
+            .data(pets)
+            .data(actix_settings.clone())
+            .data::<Arc<dyn rabbithole::policy::Policy>>(Arc::new(rabbithole::policy::AllowAll))
+            .service(
+                actix_web::web::scope(&actix_settings.path)
+                    .wrap(rabbithole_endpoint_actix::middleware::JsonApi)
+                    .service(PetService::actix_service()),
+            ),
+    )
+    .await;
+
+    let uri = format!("/api/v1/pets/{}", dog.id);
+    let doc: serde_json::Value = read_response_json(&mut app, get(&uri)).await;
+    assert_eq!(doc["data"]["type"], "dogs");
+    assert_eq!(doc["data"]["id"], dog.id.to_string());
+
+    // The enums are not `Patchable`, so they have no `PATCH` route
+    let resp = call_service(&mut app, patch(&uri, &serde_json::json!({}))).await;
+    assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(resp.headers().get(header::ALLOW).unwrap(), "GET, DELETE");
+}
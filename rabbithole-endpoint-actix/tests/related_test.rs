@@ -0,0 +1,80 @@
+#[macro_use]
+extern crate lazy_static;
+
+pub mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test::{call_service, read_response_json};
+use common::get;
+use common::model::dog::generate_dogs;
+use common::model::human::Human;
+use common::post;
+use common::service;
+use rabbithole::model::document::Document;
+use rabbithole::model::resource::IdentifierData;
+use rabbithole::operation::{IdentifierDataWrapper, ResourceDataWrapper};
+use rabbithole_endpoint_actix::ActixSettings;
+
+#[actix_rt::test]
+async fn default_related_test() {
+    let mut app = init_app!(1, 1);
+
+    let dogs = generate_dogs(2);
+    for dog in ResourceDataWrapper::from_entities(&dogs, "http://localhost:1234/api/v1") {
+        let resp = call_service(&mut app, post("/api/v1/dogs", &dog)).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
+    let human = Human::from(dogs.as_slice());
+    let master = ResourceDataWrapper::from_entities(
+        std::slice::from_ref(&human),
+        "http://localhost:1234/api/v1",
+    )
+    .pop()
+    .unwrap();
+    let resp = call_service(&mut app, post("/api/v1/people", &master)).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    // The relationship is picked from the human loaded by `fetch_single`
+    let req = get(format!("/api/v1/people/{}/relationships/dogs", human.id).as_str());
+    let relat: IdentifierDataWrapper = read_response_json(&mut app, req).await;
+    match relat.data {
+        IdentifierData::Multiple(idents) => assert_eq!(idents.len(), 2),
+        _ => unreachable!(),
+    }
+
+    // Even if the sparse fieldsets of the human leave it out
+    let req = get(format!(
+        "/api/v1/people/{}/relationships/dogs?fields[people]=name",
+        human.id
+    )
+    .as_str());
+    let relat: IdentifierDataWrapper = read_response_json(&mut app, req).await;
+    match relat.data {
+        IdentifierData::Multiple(idents) => assert_eq!(idents.len(), 2),
+        _ => unreachable!(),
+    }
+
+    // So are the related dogs
+    let req = get(format!("/api/v1/people/{}/dogs", human.id).as_str());
+    let doc: Document = read_response_json(&mut app, req).await;
+    let (dogs, _) = doc.into_multiple().unwrap();
+    assert_eq!(dogs.len(), 2);
+    assert!(dogs.iter().all(|dog| dog.id.ty == "dogs"));
+
+    let req = get(format!("/api/v1/people/{}/relationships/name", human.id).as_str());
+    let err: serde_json::Value = read_response_json(&mut app, req).await;
+    assert_eq!(err["errors"][0]["code"], "RBH-0401");
+
+    let req = get(format!("/api/v1/people/{}/cats", human.id).as_str());
+    let err: serde_json::Value = read_response_json(&mut app, req).await;
+    assert_eq!(err["errors"][0]["code"], "RBH-0401");
+
+    // A missing human is a missing parent resource
+    let missing_id = uuid::Uuid::new_v4();
+    let req = get(format!("/api/v1/people/{}/dogs", missing_id).as_str());
+    let resp = call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let req = get(format!("/api/v1/people/{}/relationships/dogs", missing_id).as_str());
+    let err: serde_json::Value = read_response_json(&mut app, req).await;
+    assert_eq!(err["errors"][0]["code"], "RBH-0404");
+}
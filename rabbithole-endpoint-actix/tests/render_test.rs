@@ -0,0 +1,73 @@
+#[macro_use]
+extern crate lazy_static;
+
+pub mod common;
+
+use actix_web::http::header;
+use actix_web::test::{read_response_json, TestRequest};
+use common::request;
+use common::service;
+use rabbithole::model::error::{Error, RabbitholeErrorKind};
+use rabbithole::render::ErrorRenderer;
+use rabbithole_endpoint_actix::ActixSettings;
+
+/// Derives the error ids from the `X-Request-Id` header, and speaks French
+struct FrenchRenderer;
+
+impl ErrorRenderer for FrenchRenderer {
+    fn error_id(&self, headers: &http::HeaderMap, index: usize, _err: &Error) -> Option<String> {
+        let request_id = headers.get("x-request-id")?.to_str().ok()?;
+        Some(format!("{}-{}", request_id, index))
+    }
+
+    fn localize(&self, err: &Error, languages: &[String]) -> Option<(String, String)> {
+        if languages.first()? != "fr" {
+            return None;
+        }
+        match err.kind()? {
+            RabbitholeErrorKind::RouteNotFound { path } => Some((
+                "Route introuvable".into(),
+                format!("Aucune route ne correspond au chemin `{}`", path),
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[actix_rt::test]
+async fn render_test() {
+    let mut app = init_app!(renderer: FrenchRenderer);
+    let uri = "/api/v1/dogs/1/relationships/master/more";
+
+    let req = request(TestRequest::get(), uri)
+        .header("x-request-id", "abc")
+        .header(header::ACCEPT_LANGUAGE, "de;q=0.5, fr, *")
+        .to_request();
+    let doc: serde_json::Value = read_response_json(&mut app, req).await;
+    assert_eq!(doc["errors"][0]["id"], "abc-0");
+    assert_eq!(doc["errors"][0]["code"], "RBH-0008");
+    assert_eq!(doc["errors"][0]["title"], "Route introuvable");
+    assert_eq!(
+        doc["errors"][0]["detail"],
+        format!("Aucune route ne correspond au chemin `{}`", uri)
+    );
+
+    // The English messages are kept for the other languages
+    let req = request(TestRequest::get(), uri)
+        .header("x-request-id", "abc")
+        .header(header::ACCEPT_LANGUAGE, "fr;q=0.8, en")
+        .to_request();
+    let doc: serde_json::Value = read_response_json(&mut app, req).await;
+    assert_eq!(doc["errors"][0]["id"], "abc-0");
+    assert_eq!(doc["errors"][0]["title"], "Route Not Found");
+
+    // The errors responded by the middleware are rendered as well
+    let req = TestRequest::get()
+        .uri("/api/v1/dogs")
+        .header(header::CONTENT_TYPE, rabbithole::JSON_API_HEADER)
+        .header("x-request-id", "def")
+        .to_request();
+    let doc: serde_json::Value = read_response_json(&mut app, req).await;
+    assert_eq!(doc["errors"][0]["id"], "def-0");
+    assert_eq!(doc["errors"][0]["code"], "RBH-0302");
+}
@@ -0,0 +1,89 @@
+#[macro_use]
+extern crate lazy_static;
+
+pub mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::test::{call_service, read_response_json};
+use common::model::dog::generate_dogs;
+use common::model::human::Human;
+use common::service;
+use common::{patch, post};
+use rabbithole::model::resource::{IdentifierData, ResourceIdentifier};
+use rabbithole::operation::ResourceDataWrapper;
+use rabbithole_endpoint_actix::ActixSettings;
+use serde_json::json;
+use std::collections::HashMap;
+use std::iter::FromIterator;
+
+#[actix_rt::test]
+async fn validate_test() {
+    let mut app = init_app!(1, 1);
+
+    let dogs = generate_dogs(1);
+    let dog_resources = ResourceDataWrapper::from_entities(&dogs, "http://localhost:1234/api/v1");
+    let mut dog_resource = dog_resources[0].clone();
+
+    // An empty name of a dog
+    let attrs: HashMap<String, serde_json::Value> =
+        HashMap::from_iter(vec![("name".to_string(), json!(""))]);
+    dog_resource.data.attributes = attrs.into();
+    let req = post("/api/v1/dogs", &dog_resource);
+    let resp = call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let doc: serde_json::Value =
+        read_response_json(&mut app, post("/api/v1/dogs", &dog_resource)).await;
+    assert_eq!(doc["errors"][0]["code"], "RBH-0211");
+    assert_eq!(
+        doc["errors"][0]["source"]["pointer"],
+        "/data/attributes/name"
+    );
+
+    // All of the invalid fields of a human are reported at once
+    let human: Human = dogs.as_slice().into();
+    let mut human_resource = ResourceDataWrapper::from_entities(
+        std::slice::from_ref(&human),
+        "http://localhost:1234/api/v1",
+    )
+    .pop()
+    .unwrap();
+    let attrs: HashMap<String, serde_json::Value> =
+        HashMap::from_iter(vec![("name".to_string(), json!("Alice Smith"))]);
+    human_resource.data.attributes = attrs.into();
+    human_resource
+        .data
+        .relationships
+        .get_mut("dogs")
+        .unwrap()
+        .data = Some(IdentifierData::Multiple(
+        (0 .. 9)
+            .map(|i| ResourceIdentifier::new("dogs", &i.to_string()))
+            .collect(),
+    ));
+    let doc: serde_json::Value =
+        read_response_json(&mut app, post("/api/v1/people", &human_resource)).await;
+    let errors = doc["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0]["code"], "RBH-0213");
+    assert_eq!(errors[0]["source"]["pointer"], "/data/attributes/name");
+    assert_eq!(errors[1]["code"], "RBH-0211");
+    assert_eq!(errors[1]["source"]["pointer"], "/data/relationships/dogs");
+
+    // The required fields can be missing in a patch, but cannot be null
+    dog_resource.data.attributes = Default::default();
+    let resp = call_service(&mut app, post("/api/v1/dogs", &dog_resources[0])).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let uri = format!("/api/v1/dogs/{}", dogs[0].id);
+    let resp = call_service(&mut app, patch(&uri, &dog_resource)).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let attrs: HashMap<String, serde_json::Value> =
+        HashMap::from_iter(vec![("name".to_string(), serde_json::Value::Null)]);
+    dog_resource.data.attributes = attrs.into();
+    let doc: serde_json::Value = read_response_json(&mut app, patch(&uri, &dog_resource)).await;
+    assert_eq!(doc["errors"][0]["code"], "RBH-0210");
+    assert_eq!(
+        doc["errors"][0]["source"]["pointer"],
+        "/data/attributes/name"
+    );
+}
@@ -1,15 +1,22 @@
 use crate::model::document::{Document, Included};
+use crate::model::error::{self, ErrorSource};
 use crate::model::link::{Link, Links};
-use crate::model::relationship::{RelationshipLinks, Relationships};
-use crate::model::resource::{Attributes, Resource, ResourceIdentifier};
+use crate::model::relationship::{Linkage, RelationshipLinks, Relationships};
+use crate::model::resource::{Attributes, IdentifierData, Resource, ResourceIdentifier};
 use crate::model::Meta;
+use crate::policy::FieldVisibility;
 use crate::query::*;
 use crate::Result;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::hash::Hash;
 use std::iter::FromIterator;
 use std::ops::Deref;
+use std::str::FromStr;
 
 pub trait Entity: Serialize + Clone {
     /// Returns the `included` field of this entity
@@ -22,9 +29,13 @@ pub trait Entity: Serialize + Clone {
     /// `fields_query`: For any resources whose `ty` is in the `fields_query`, their `relationship`
     ///                 and `attributes` will be filtered. Only the field name inside the `field_query`
     ///                 item will be retained
+    ///
+    /// `visibility`: The fields which cannot be read will be removed, and so will the resources of
+    ///               the relationships which cannot be read
     #[doc(hidden)]
     fn included(
         &self, uri: &str, include_query: &Option<IncludeQuery>, fields_query: &FieldsQuery,
+        visibility: &dyn FieldVisibility,
     ) -> Result<Included>;
 
     /// Returns a `Document` based on `query`. This function will do all of the actions databases should do in memory,
@@ -37,10 +48,25 @@ pub trait Entity: Serialize + Clone {
 }
 
 pub trait SingleEntity: Entity {
+    /// The type of the id field, which is parsed from the routes before being handed to the
+    /// services, and rendered with `Display` in the documents
+    type Id: FromStr + Display + Send + Sync;
+
     #[doc(hidden)]
     fn ty() -> String;
     #[doc(hidden)]
     fn id(&self) -> String;
+    /// Parses the id in a route, an invalid one is responded as `404 Not Found`
+    #[doc(hidden)]
+    fn parse_id(id: &str) -> Result<Self::Id>;
+    /// If a request body of the type `ty` can be posted to the routes of this entity, which is
+    /// its own type, or the type of any variant of an enum of entities
+    #[doc(hidden)]
+    fn accepts_type(ty: &str) -> bool { ty == Self::ty() }
+    /// If the attribute or relationship `field` can be written by the clients, which is not
+    /// read-only
+    #[doc(hidden)]
+    fn is_writable(_field: &str) -> bool { true }
     #[doc(hidden)]
     fn attributes(&self) -> Attributes;
     #[doc(hidden)]
@@ -48,15 +74,24 @@ pub trait SingleEntity: Entity {
 
     #[doc(hidden)]
     fn links(&self, uri: &str) -> Links {
-        let slf = format!(
-            "{uri}/{ty}/{id}",
-            uri = uri,
-            ty = <Self as SingleEntity>::ty(),
-            id = self.id()
-        )
-        .parse::<Link>()
-        .unwrap();
-        HashMap::from_iter(vec![("self".into(), slf)])
+        resource_links(&<Self as SingleEntity>::ty(), &self.id(), uri)
+    }
+
+    /// The `meta` of the resource
+    #[doc(hidden)]
+    fn meta(&self) -> Meta { Default::default() }
+
+    /// When the `data` of the relationship `field` is rendered
+    #[doc(hidden)]
+    fn linkage(&self, _field: &str) -> Linkage { Linkage::Always }
+
+    /// The document of the resources in the relationship `field`, which is the response of the
+    /// related resource route
+    #[doc(hidden)]
+    fn related_document(
+        &self, field: &str, _uri: &str, _query: &Query, _request_path: http::Uri,
+    ) -> Result<Document> {
+        Err(error::Error::FieldNotExist(field, None))
     }
 
     fn to_document(
@@ -65,9 +100,13 @@ pub trait SingleEntity: Entity {
     ) -> Result<Document> {
         let (key, value) = Link::slf(uri, request_path);
         additional_links.insert(key, value);
+        let mut resource = self
+            .to_resource(uri, &query.fields, query.visibility())
+            .unwrap();
+        retain_linkage(self, &mut resource, &query.include);
         let mut doc = Document::single_resource(
-            self.to_resource(uri, &query.fields).unwrap(),
-            self.included(uri, &query.include, &query.fields)?,
+            resource,
+            self.included(uri, &query.include, &query.fields, query.visibility())?,
         );
         doc.extend_links(additional_links);
         doc.extend_meta(additional_meta);
@@ -81,13 +120,28 @@ pub trait SingleEntity: Entity {
         })
     }
 
-    fn to_resource(&self, uri: &str, fields_query: &FieldsQuery) -> Option<Resource> {
+    fn to_resource(
+        &self, uri: &str, fields_query: &FieldsQuery, visibility: &dyn FieldVisibility,
+    ) -> Option<Resource> {
+        let ty = <Self as SingleEntity>::ty();
         let mut attributes = self.attributes();
         let mut relationships = self.relationships(uri);
+        let mut meta = self.meta();
         for (k, vs) in fields_query.iter() {
-            if &<Self as SingleEntity>::ty() == k {
+            if &ty == k {
                 attributes = attributes.retain(vs);
                 relationships.retain(|k, _| vs.contains(k));
+                meta.retain(|k, _| vs.contains(k));
+            }
+        }
+        attributes = attributes.retain_by(|k| visibility.can_read_field(&ty, k));
+        relationships.retain(|k, _| visibility.can_read_field(&ty, k));
+        // The keys of `meta` are rendered from the fields and methods, so they are hidden like
+        // the fields as well
+        meta.retain(|k, _| visibility.can_read_field(&ty, k));
+        for (field, relat) in relationships.iter_mut() {
+            if self.linkage(field) == Linkage::Never {
+                relat.data = None;
             }
         }
 
@@ -99,7 +153,7 @@ pub trait SingleEntity: Entity {
             attributes,
             relationships,
             links: self.links(uri),
-            ..Default::default()
+            meta,
         })
     }
 
@@ -130,16 +184,39 @@ pub trait SingleEntity: Entity {
 }
 
 impl<T: SingleEntity> SingleEntity for Option<T> {
+    type Id = T::Id;
+
     fn ty() -> String { T::ty() }
 
     fn id(&self) -> String { self.as_ref().map(SingleEntity::id).unwrap() }
 
+    fn parse_id(id: &str) -> Result<Self::Id> { T::parse_id(id) }
+
+    fn accepts_type(ty: &str) -> bool { T::accepts_type(ty) }
+
+    fn is_writable(field: &str) -> bool { T::is_writable(field) }
+
     fn attributes(&self) -> Attributes { self.as_ref().map(SingleEntity::attributes).unwrap() }
 
     fn relationships(&self, uri: &str) -> Relationships {
         self.as_ref().map(|op| op.relationships(uri)).unwrap()
     }
 
+    fn linkage(&self, field: &str) -> Linkage {
+        self.as_ref()
+            .map_or(Linkage::Always, |op| op.linkage(field))
+    }
+
+    fn related_document(
+        &self, field: &str, uri: &str, query: &Query, request_path: http::Uri,
+    ) -> Result<Document> {
+        if let Some(item) = self {
+            item.related_document(field, uri, query, request_path)
+        } else {
+            Err(error::Error::ParentResourceNotExist(field, None))
+        }
+    }
+
     fn to_document(
         &self, uri: &str, query: &Query, request_path: http::Uri, additional_links: Links,
         additional_meta: Meta,
@@ -162,17 +239,21 @@ impl<T: SingleEntity> SingleEntity for Option<T> {
         self.as_ref().and_then(SingleEntity::to_resource_identifier)
     }
 
-    fn to_resource(&self, uri: &str, query: &FieldsQuery) -> Option<Resource> {
-        self.as_ref().and_then(|e| e.to_resource(uri, query))
+    fn to_resource(
+        &self, uri: &str, query: &FieldsQuery, visibility: &dyn FieldVisibility,
+    ) -> Option<Resource> {
+        self.as_ref()
+            .and_then(|e| e.to_resource(uri, query, visibility))
     }
 }
 
 impl<T: Entity> Entity for Option<T> {
     fn included(
         &self, uri: &str, include_query: &Option<IncludeQuery>, fields_query: &FieldsQuery,
+        visibility: &dyn FieldVisibility,
     ) -> Result<Included> {
         if let Some(s) = self {
-            s.included(uri, include_query, fields_query)
+            s.included(uri, include_query, fields_query, visibility)
         } else {
             Ok(Default::default())
         }
@@ -189,20 +270,43 @@ impl<T: Entity> Entity for Option<T> {
 }
 
 impl<T: SingleEntity> SingleEntity for Box<T> {
+    type Id = T::Id;
+
     fn ty() -> String { T::ty() }
 
     fn id(&self) -> String { self.as_ref().id() }
 
+    fn parse_id(id: &str) -> Result<Self::Id> { T::parse_id(id) }
+
+    fn accepts_type(ty: &str) -> bool { T::accepts_type(ty) }
+
+    fn is_writable(field: &str) -> bool { T::is_writable(field) }
+
     fn attributes(&self) -> Attributes { self.as_ref().attributes() }
 
     fn relationships(&self, uri: &str) -> Relationships { self.as_ref().relationships(uri) }
+
+    fn links(&self, uri: &str) -> Links { self.as_ref().links(uri) }
+
+    fn meta(&self) -> Meta { self.as_ref().meta() }
+
+    fn linkage(&self, field: &str) -> Linkage { self.as_ref().linkage(field) }
+
+    fn related_document(
+        &self, field: &str, uri: &str, query: &Query, request_path: http::Uri,
+    ) -> Result<Document> {
+        self.as_ref()
+            .related_document(field, uri, query, request_path)
+    }
 }
 
 impl<T: Entity> Entity for Box<T> {
     fn included(
         &self, uri: &str, include_query: &Option<IncludeQuery>, fields_query: &FieldsQuery,
+        visibility: &dyn FieldVisibility,
     ) -> Result<Included> {
-        self.as_ref().included(uri, include_query, fields_query)
+        self.as_ref()
+            .included(uri, include_query, fields_query, visibility)
     }
 
     fn to_document(
@@ -218,13 +322,33 @@ impl<T: SingleEntity> SingleEntity for &T
 where
     Self: Clone,
 {
+    type Id = T::Id;
+
     fn ty() -> String { T::ty() }
 
     fn id(&self) -> String { self.deref().id() }
 
+    fn parse_id(id: &str) -> Result<Self::Id> { T::parse_id(id) }
+
+    fn accepts_type(ty: &str) -> bool { T::accepts_type(ty) }
+
+    fn is_writable(field: &str) -> bool { T::is_writable(field) }
+
     fn attributes(&self) -> Attributes { self.deref().attributes() }
 
     fn relationships(&self, uri: &str) -> Relationships { self.deref().relationships(uri) }
+
+    fn links(&self, uri: &str) -> Links { T::links(self, uri) }
+
+    fn meta(&self) -> Meta { T::meta(self) }
+
+    fn linkage(&self, field: &str) -> Linkage { T::linkage(self, field) }
+
+    fn related_document(
+        &self, field: &str, uri: &str, query: &Query, request_path: http::Uri,
+    ) -> Result<Document> {
+        T::related_document(self, field, uri, query, request_path)
+    }
 }
 
 impl<T: Entity> Entity for &T
@@ -233,8 +357,10 @@ where
 {
     fn included(
         &self, uri: &str, include_query: &Option<IncludeQuery>, fields_query: &FieldsQuery,
+        visibility: &dyn FieldVisibility,
     ) -> Result<Included> {
-        self.deref().included(uri, include_query, fields_query)
+        self.deref()
+            .included(uri, include_query, fields_query, visibility)
     }
 
     fn to_document(
@@ -249,10 +375,11 @@ where
 impl<T: SingleEntity> Entity for &[T] {
     fn included(
         &self, uri: &str, include_query: &Option<IncludeQuery>, fields_query: &FieldsQuery,
+        visibility: &dyn FieldVisibility,
     ) -> Result<Included> {
         let includes: Vec<Included> = self
             .iter()
-            .map(|e| e.included(uri, include_query, fields_query))
+            .map(|e| e.included(uri, include_query, fields_query, visibility))
             .collect::<Result<Vec<Included>>>()?;
         Ok(includes.into_iter().flat_map(|s| s.into_iter()).collect())
     }
@@ -265,12 +392,16 @@ impl<T: SingleEntity> Entity for &[T] {
         let (key, value) = Link::slf(uri, request_path);
         let resources = entities
             .iter()
-            .filter_map(|e| e.to_resource(uri, &query.fields))
+            .filter_map(|e| {
+                let mut resource = e.to_resource(uri, &query.fields, query.visibility())?;
+                retain_linkage(e, &mut resource, &query.include);
+                Some(resource)
+            })
             .collect();
         additional_links.insert(key, value);
         let mut doc = Document::multiple_resources(
             resources,
-            self.included(uri, &query.include, &query.fields)?,
+            self.included(uri, &query.include, &query.fields, query.visibility())?,
         );
         doc.extend_links(additional_links);
         doc.extend_meta(additional_meta);
@@ -282,8 +413,10 @@ impl<T: SingleEntity> Entity for &[T] {
 impl<T: SingleEntity> Entity for Vec<T> {
     fn included(
         &self, uri: &str, include_query: &Option<IncludeQuery>, fields_query: &FieldsQuery,
+        visibility: &dyn FieldVisibility,
     ) -> Result<Included> {
-        self.as_slice().included(uri, include_query, fields_query)
+        self.as_slice()
+            .included(uri, include_query, fields_query, visibility)
     }
 
     fn to_document(
@@ -294,3 +427,331 @@ impl<T: SingleEntity> Entity for Vec<T> {
             .to_document(uri, query, request_path, additional_links, additional_meta)
     }
 }
+
+/// Builds an entity out of the `Resource` in a request body, which is derived by `EntityDecorator`
+///
+/// The `attributes` are deserialized into the fields directly, while the resource identifiers in
+/// the `relationships` are resolved into the related entities by `lookup`, usually the services
+/// of the related types. A missing attribute is treated as `null`, so only the `Option` fields
+/// can be left out
+#[async_trait]
+pub trait FromResource<L: Sync>: Sized {
+    async fn from_resource(resource: &Resource, lookup: &L) -> Result<Self>;
+}
+
+/// An entity which can be partially updated by the body of a `PATCH` request, which is derived by
+/// `EntityDecorator` with a `<Entity>Patch` struct
+///
+/// The `Patch` holds every attribute and relationship of the entity in an `Option`, where `None`
+/// means the field is missing in the body and should be left untouched. It is built by
+/// `FromResource` as well, with the service handling the request as the lookup
+pub trait Patchable: SingleEntity {
+    type Patch: Send;
+
+    fn apply_patch(&mut self, patch: Self::Patch);
+}
+
+/// Resolves the resource identifiers of the relationship `field` into a field of type `T`
+///
+/// Implemented for `Vec<E>`, `HashSet<E>`, `Option<E>` and `Box<E>` by every `EntityLookup<E>`
+#[async_trait]
+pub trait RelationshipLookup<T>: Sync {
+    async fn lookup_relationship(&self, field: &str, data: &IdentifierData) -> Result<T>;
+}
+
+/// Resolves the resource identifiers into the entities in a batch
+#[async_trait]
+pub trait EntityLookup<E>: Sync {
+    /// Returns the entities in the order of `ids`, or an error if any of them does not exist
+    async fn lookup_entities(&self, ids: &[ResourceIdentifier]) -> Result<Vec<E>>;
+}
+
+#[async_trait]
+impl<E: Send, L: EntityLookup<E>> EntityLookup<Box<E>> for L {
+    async fn lookup_entities(&self, ids: &[ResourceIdentifier]) -> Result<Vec<Box<E>>> {
+        let entities: Vec<E> = self.lookup_entities(ids).await?;
+        Ok(entities.into_iter().map(Box::new).collect())
+    }
+}
+
+#[async_trait]
+impl<E: Send, L: EntityLookup<E>> RelationshipLookup<Vec<E>> for L {
+    async fn lookup_relationship(&self, _field: &str, data: &IdentifierData) -> Result<Vec<E>> {
+        self.lookup_entities(&data.data()).await
+    }
+}
+
+#[async_trait]
+impl<E: Send + Eq + Hash, L: EntityLookup<E>> RelationshipLookup<HashSet<E>> for L {
+    async fn lookup_relationship(&self, _field: &str, data: &IdentifierData) -> Result<HashSet<E>> {
+        let entities: Vec<E> = self.lookup_entities(&data.data()).await?;
+        Ok(entities.into_iter().collect())
+    }
+}
+
+#[async_trait]
+impl<E: Send, L: EntityLookup<E>> RelationshipLookup<Option<E>> for L {
+    async fn lookup_relationship(&self, field: &str, data: &IdentifierData) -> Result<Option<E>> {
+        match data {
+            IdentifierData::Single(None) => Ok(None),
+            IdentifierData::Single(Some(id)) => {
+                let mut entities: Vec<E> = self.lookup_entities(std::slice::from_ref(id)).await?;
+                entities.pop().map(Some).ok_or_else(|| {
+                    error::Error::InvalidFieldValue(
+                        field,
+                        "the related resource does not exist",
+                        Some(relationship_pointer(field)),
+                    )
+                })
+            },
+            IdentifierData::Multiple(_) => Err(error::Error::InvalidFieldValue(
+                field,
+                "a to-one relationship is expected",
+                Some(relationship_pointer(field)),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl<E: Send, L: EntityLookup<E>> RelationshipLookup<Box<E>> for L {
+    async fn lookup_relationship(&self, field: &str, data: &IdentifierData) -> Result<Box<E>> {
+        let entity: Option<E> = self.lookup_relationship(field, data).await?;
+        entity
+            .map(Box::new)
+            .ok_or_else(|| error::Error::MissingField(field, Some(relationship_pointer(field))))
+    }
+}
+
+/// The linkage of the relationship `field` in the body `resource`, where the one without `data`
+/// is left untouched just like an absent one
+fn relationship_data<'a>(resource: &'a Resource, field: &str) -> Option<&'a IdentifierData> {
+    resource
+        .relationships
+        .get(field)
+        .and_then(|relat| relat.data.as_ref())
+}
+
+fn relationship_pointer(field: &str) -> ErrorSource {
+    ErrorSource::from_pointer(&format!("/data/relationships/{}", field))
+}
+
+#[doc(hidden)]
+pub fn id_from_resource<T>(resource: &Resource) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let pointer = || Some(ErrorSource::from_pointer("/data/id"));
+    if resource.id.id.is_empty() {
+        return Err(error::Error::MissingField("id", pointer()));
+    }
+    resource
+        .id
+        .id
+        .parse()
+        .map_err(|err: T::Err| error::Error::InvalidFieldValue("id", &err.to_string(), pointer()))
+}
+
+#[doc(hidden)]
+pub fn id_from_path<T>(id: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    id.parse().map_err(|err: T::Err| {
+        error::Error::InvalidPathParameter(&format!("invalid id `{}`, {}", id, err), None)
+    })
+}
+
+/// The json value of the attribute or `meta` key `name` of the entity `ty`, where the fields not
+/// skipped must be serializable, so the failing ones panic rather than being silently dropped
+#[doc(hidden)]
+pub fn field_value<T: Serialize + ?Sized>(value: &T, ty: &str, name: &str) -> serde_json::Value {
+    serde_json::to_value(value).unwrap_or_else(|err| {
+        panic!(
+            "the field `{}` of the entity `{}` cannot be serialized: {}",
+            name, ty, err
+        )
+    })
+}
+
+/// Leaves out the `data` of the relationships of the primary data `resource`, which are linked
+/// only when included but not in the `include` query, or without the `include` query at all
+fn retain_linkage<E: SingleEntity>(
+    entity: &E, resource: &mut Resource, include: &Option<IncludeQuery>,
+) {
+    for (field, relat) in resource.relationships.iter_mut() {
+        if entity.linkage(field) == Linkage::WhenIncluded
+            && !include
+                .as_ref()
+                .is_some_and(|include| include.contains(field))
+        {
+            relat.data = None;
+        }
+    }
+}
+
+/// The links of the resource of `ty` with `id`, where there is only the `self` one
+#[doc(hidden)]
+pub fn resource_links(ty: &str, id: &str, uri: &str) -> Links {
+    let slf = format!("{uri}/{ty}/{id}", uri = uri, ty = ty, id = id)
+        .parse::<Link>()
+        .unwrap();
+    HashMap::from_iter(vec![("self".into(), slf)])
+}
+
+/// The `meta` of a to-many relationship decorated by `#[entity(count)]`
+#[doc(hidden)]
+pub fn count_meta(count: usize) -> Meta { HashMap::from_iter(vec![("count".into(), count.into())]) }
+
+/// The error of a `resource` whose type is none of the `expected` ones of the variants of a
+/// polymorphic entity
+#[doc(hidden)]
+pub fn unknown_variant(resource: &Resource, expected: &[String]) -> error::Error {
+    error::Error::TypeNotMatch(
+        &format!("one of [{}]", expected.join(", ")),
+        &resource.id.ty,
+        Some(ErrorSource::from_pointer("/data/type")),
+    )
+}
+
+#[doc(hidden)]
+pub fn attribute_from_resource<T: DeserializeOwned>(resource: &Resource, field: &str) -> Result<T> {
+    let pointer = || {
+        Some(ErrorSource::from_pointer(&format!(
+            "/data/attributes/{}",
+            field
+        )))
+    };
+    match resource.attributes.get_field(field) {
+        Ok(value) => serde_json::from_value(value.0.clone())
+            .map_err(|err| error::Error::InvalidFieldValue(field, &err.to_string(), pointer())),
+        Err(_) => serde_json::from_value(serde_json::Value::Null)
+            .map_err(|_| error::Error::MissingField(field, pointer())),
+    }
+}
+
+#[doc(hidden)]
+pub fn patch_attribute_from_resource<T: DeserializeOwned>(
+    resource: &Resource, field: &str,
+) -> Result<Option<T>> {
+    if resource.attributes.get_field(field).is_ok() {
+        attribute_from_resource(resource, field).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+#[doc(hidden)]
+pub async fn patch_relationship_from_resource<T, L>(
+    resource: &Resource, field: &str, lookup: &L,
+) -> Result<Option<T>>
+where
+    L: RelationshipLookup<T>,
+{
+    match relationship_data(resource, field) {
+        Some(data) => lookup.lookup_relationship(field, data).await.map(Some),
+        None => Ok(None),
+    }
+}
+
+#[doc(hidden)]
+pub async fn relationship_from_resource<T, L>(
+    resource: &Resource, field: &str, lookup: &L,
+) -> Result<T>
+where
+    L: RelationshipLookup<T>,
+{
+    match relationship_data(resource, field) {
+        Some(data) => lookup.lookup_relationship(field, data).await,
+        None => {
+            lookup
+                .lookup_relationship(field, &IdentifierData::Single(None))
+                .await
+        },
+    }
+}
+
+/// Parses the ids of a relationship holding only the ids, whose resources should be of type `ty`
+fn lazy_ids<I>(field: &str, ty: &str, ids: &[ResourceIdentifier]) -> Result<Vec<I>>
+where
+    I: FromStr,
+    I::Err: Display,
+{
+    ids.iter()
+        .map(|id| {
+            if id.ty != ty {
+                return Err(error::Error::TypeNotMatch(
+                    ty,
+                    &id.ty,
+                    Some(relationship_pointer(field)),
+                ));
+            }
+            id.id.parse().map_err(|err: I::Err| {
+                error::Error::InvalidFieldValue(
+                    field,
+                    &err.to_string(),
+                    Some(relationship_pointer(field)),
+                )
+            })
+        })
+        .collect()
+}
+
+#[doc(hidden)]
+pub fn lazy_identifier<I: Display>(ty: &str, id: &I) -> ResourceIdentifier {
+    ResourceIdentifier {
+        ty: ty.to_string(),
+        id: id.to_string(),
+    }
+}
+
+#[doc(hidden)]
+pub fn lazy_optional_to_one_from_resource<I>(
+    resource: &Resource, field: &str, ty: &str,
+) -> Result<Option<I>>
+where
+    I: FromStr,
+    I::Err: Display,
+{
+    match relationship_data(resource, field) {
+        None | Some(IdentifierData::Single(None)) => Ok(None),
+        Some(IdentifierData::Single(Some(id))) => {
+            Ok(lazy_ids(field, ty, std::slice::from_ref(id))?.pop())
+        },
+        Some(IdentifierData::Multiple(_)) => Err(error::Error::InvalidFieldValue(
+            field,
+            "a to-one relationship is expected",
+            Some(relationship_pointer(field)),
+        )),
+    }
+}
+
+#[doc(hidden)]
+pub fn lazy_to_one_from_resource<I>(resource: &Resource, field: &str, ty: &str) -> Result<I>
+where
+    I: FromStr,
+    I::Err: Display,
+{
+    lazy_optional_to_one_from_resource(resource, field, ty)?
+        .ok_or_else(|| error::Error::MissingField(field, Some(relationship_pointer(field))))
+}
+
+#[doc(hidden)]
+pub fn lazy_to_many_from_resource<C, I>(resource: &Resource, field: &str, ty: &str) -> Result<C>
+where
+    C: FromIterator<I>,
+    I: FromStr,
+    I::Err: Display,
+{
+    match relationship_data(resource, field) {
+        None => Ok(std::iter::empty().collect()),
+        Some(IdentifierData::Multiple(ids)) => Ok(lazy_ids(field, ty, ids)?.into_iter().collect()),
+        Some(IdentifierData::Single(_)) => Err(error::Error::InvalidFieldValue(
+            field,
+            "a to-many relationship is expected",
+            Some(relationship_pointer(field)),
+        )),
+    }
+}
@@ -0,0 +1,137 @@
+use crate::model::document::{Document, DocumentItem, Included, PrimaryDataItem};
+use crate::model::error::{Error, ErrorSource};
+use crate::model::resource::{IdentifierData, Resource, ResourceIdentifier};
+use crate::query::Query;
+use crate::Result;
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Loads the resources of a type for the `included` of the documents, usually from the service
+/// of the type, which should fetch all of them in one query
+#[async_trait]
+pub trait IncludeLoader: Send + Sync {
+    /// Returns the resources of `ids`, all of which are of the type the loader is registered
+    /// for. The ones which do not exist are left out
+    async fn load(&self, ids: &[ResourceIdentifier], uri: &str) -> Result<Vec<Resource>>;
+}
+
+/// The `IncludeLoader`s by the resource types, which resolve the `include` paths, the nested ones
+/// like `dogs.fleas` included, level by level, calling each loader at most once per level
+#[derive(Default, Clone)]
+pub struct IncludeLoaders(HashMap<String, Arc<dyn IncludeLoader>>);
+
+impl IncludeLoaders {
+    pub fn register(mut self, ty: &str, loader: impl IncludeLoader + 'static) -> Self {
+        self.0.insert(ty.to_string(), Arc::new(loader));
+        self
+    }
+
+    /// Adds the resources on the `include` paths of `query` to `doc`, which are not included yet,
+    /// where `doc` is built with `Query::shallow_include`
+    ///
+    /// The paths are resolved before the `fields` of `query` are applied, which could leave out
+    /// the relationships on the paths, so the sparse fieldsets are applied to all of the resources
+    /// in `doc` here, while the loaded ones are filtered by the `visibility` of `query` as well
+    pub async fn include(&self, mut doc: Document, uri: &str, query: &Query) -> Result<Document> {
+        if let DocumentItem::PrimaryData(Some((ref mut data, ref mut included))) = doc.item {
+            if let Some(include) = &query.include {
+                let paths: Vec<Vec<&str>> = include
+                    .iter()
+                    .map(|path| path.split('.').collect())
+                    .collect();
+                self.resolve(data, included, &paths, uri).await?;
+            }
+            match data {
+                PrimaryDataItem::Single(resource) => sparse(resource, query),
+                PrimaryDataItem::Multiple(resources) => {
+                    resources.iter_mut().for_each(|r| sparse(r, query))
+                },
+            }
+            included.values_mut().for_each(|r| sparse(r, query));
+        }
+        Ok(doc)
+    }
+
+    /// Adds the resources reached by `paths` from `data` to `included`, loading the missing ones
+    async fn resolve(
+        &self, data: &PrimaryDataItem, included: &mut Included, paths: &[Vec<&str>], uri: &str,
+    ) -> Result<()> {
+        let data = data.data();
+        let primary: HashSet<ResourceIdentifier> = data.iter().map(|r| r.id.clone()).collect();
+        let mut known: Included = included.clone();
+        known.extend(data.into_iter().map(|r| (r.id.clone(), r)));
+        // The resources reached by the path prefixes, where the empty one reaches the primary data
+        let mut reached: HashMap<&[&str], Vec<ResourceIdentifier>> = Default::default();
+        let root: &[&str] = &[];
+        reached.insert(root, primary.iter().cloned().collect());
+
+        let depth = paths.iter().map(Vec::len).max().unwrap_or_default();
+        for level in 1 ..= depth {
+            let mut targets: HashMap<&[&str], Vec<ResourceIdentifier>> = Default::default();
+            for path in paths.iter().filter(|p| p.len() >= level) {
+                let prefix = &path[.. level];
+                let ids = reached[&prefix[.. level - 1]]
+                    .iter()
+                    .filter_map(|id| known.get(id))
+                    .filter_map(|r| r.relationships.get(prefix[level - 1]))
+                    .filter_map(|relat| relat.data.as_ref())
+                    .flat_map(IdentifierData::data)
+                    .collect();
+                targets.insert(prefix, ids);
+            }
+
+            let mut missing: HashMap<&str, (&[&str], Vec<ResourceIdentifier>)> = Default::default();
+            for (prefix, ids) in &targets {
+                for id in ids.iter().filter(|id| !known.contains_key(id)) {
+                    let (_, ty_ids) = missing.entry(&id.ty).or_insert((prefix, vec![]));
+                    if !ty_ids.contains(id) {
+                        ty_ids.push(id.clone());
+                    }
+                }
+            }
+            for (ty, (prefix, ids)) in missing {
+                let loader = self.0.get(ty).ok_or_else(|| {
+                    Error::RelationshipPathNotSupported(
+                        &prefix.join("."),
+                        Some(ErrorSource::from_parameter("include")),
+                    )
+                })?;
+                for resource in loader.load(&ids, uri).await? {
+                    known.insert(resource.id.clone(), resource);
+                }
+            }
+            reached.extend(targets);
+        }
+
+        for id in reached.values().flatten() {
+            if primary.contains(id) || included.contains_key(id) {
+                continue;
+            }
+            if let Some(resource) = known.remove(id) {
+                included.insert(id.clone(), resource);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Retains the fields and the `meta` keys of `resource` which are in the `fields` of `query`, and
+/// visible to the client
+fn sparse(resource: &mut Resource, query: &Query) {
+    let ty = resource.id.ty.clone();
+    let mut attributes = std::mem::take(&mut resource.attributes);
+    if let Some(fields) = query.fields.get(&ty) {
+        attributes = attributes.retain(fields);
+        resource.relationships.retain(|k, _| fields.contains(k));
+        resource.meta.retain(|k, _| fields.contains(k));
+    }
+    let visibility = query.visibility();
+    resource.attributes = attributes.retain_by(|k| visibility.can_read_field(&ty, k));
+    resource
+        .relationships
+        .retain(|k, _| visibility.can_read_field(&ty, k));
+    resource
+        .meta
+        .retain(|k, _| visibility.can_read_field(&ty, k));
+}
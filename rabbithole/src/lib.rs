@@ -1,6 +1,5 @@
 #![feature(associated_type_defaults)]
 #![feature(step_trait)]
-#![feature(step_trait_ext)]
 
 #[macro_use]
 extern crate serde_derive;
@@ -9,11 +8,21 @@ extern crate lazy_static;
 
 use crate::model::error::Error;
 
+pub use async_trait::async_trait;
+#[doc(hidden)]
+pub use lazy_static::lazy_static;
+#[doc(hidden)]
+pub use regex;
+
 pub type Result<T> = std::result::Result<T, Error>;
 pub const JSON_API_HEADER: &str = "application/vnd.api+json";
 
 pub mod entity;
+pub mod include;
 pub mod model;
 pub mod operation;
+pub mod policy;
 pub mod query;
+pub mod render;
 pub mod rule;
+pub mod validation;
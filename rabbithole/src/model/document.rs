@@ -55,6 +55,14 @@ impl Document {
         }
     }
 
+    /// A top-level document with the `errors` array
+    pub fn errors(errors: Vec<error::Error>) -> Self {
+        Self {
+            item: DocumentItem::Errors(errors),
+            ..Default::default()
+        }
+    }
+
     pub fn into_single(self) -> Result<(Box<Resource>, Included), Self> {
         if let DocumentItem::PrimaryData(Some((PrimaryDataItem::Single(resource), included))) =
             self.item
@@ -97,6 +105,10 @@ impl Document {
     pub fn extend_links(&mut self, links: Links) { self.links.extend(links.into_iter()); }
 }
 
+impl From<error::Errors> for Document {
+    fn from(errors: error::Errors) -> Self { Document::errors(errors.0) }
+}
+
 impl Serialize for Document {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -14,6 +14,11 @@ pub struct ErrorLinks {
 
 impl ErrorLinks {
     pub fn is_empty(&self) -> bool { self.about.is_none() && self.links.is_empty() }
+
+    /// The link to the further details about the error
+    pub fn about(&self) -> Option<&Link> { self.about.as_ref() }
+
+    pub fn set_about(&mut self, about: Link) { self.about = Some(about); }
 }
 
 impl From<Links> for ErrorLinks {
@@ -26,17 +31,36 @@ impl From<Links> for ErrorLinks {
 /// Error location
 #[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ErrorSource {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pointer: Option<crate::model::link::WrappedUri>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub parameter: Option<String>,
 }
 
 impl ErrorSource {
     pub(crate) fn is_empty(&self) -> bool { self.pointer.is_none() && self.parameter.is_none() }
+
+    /// A JSON Pointer to the value in the request document that caused the error,
+    /// like `/data/attributes/title`
+    pub fn from_pointer(pointer: &str) -> Self {
+        Self {
+            pointer: pointer.parse().ok(),
+            parameter: None,
+        }
+    }
+
+    /// The query parameter that caused the error, like `page[size]`
+    pub fn from_parameter(parameter: &str) -> Self {
+        Self {
+            pointer: None,
+            parameter: Some(parameter.to_string()),
+        }
+    }
 }
 
 /// JSON-API Error
 /// All fields are optional
-#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Error {
     /// a unique identifier for this particular occurrence of the problem
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -61,6 +85,51 @@ pub struct Error {
     pub source: ErrorSource,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub meta: Option<Meta>,
+    /// The parts of the error kept only in the server, see `Error::kind` and `Error::headers`
+    #[doc(hidden)]
+    #[serde(skip)]
+    pub internals: Option<Box<ErrorInternals>>,
+}
+
+/// The parts of an `Error` which are neither serialized nor compared, boxed to keep `Error` small
+#[derive(Debug, Clone, Default)]
+pub struct ErrorInternals {
+    kind: Option<RabbitholeErrorKind>,
+    headers: http::HeaderMap,
+}
+
+impl Error {
+    /// The typed kind of the error if it is one of Rabbithole's, which is not serialized
+    pub fn kind(&self) -> Option<&RabbitholeErrorKind> {
+        self.internals
+            .as_ref()
+            .and_then(|internals| internals.kind.as_ref())
+    }
+
+    /// The headers responded along with the error, like `Retry-After`, which are not serialized
+    pub fn headers(&self) -> Option<&http::HeaderMap> {
+        self.internals.as_ref().map(|internals| &internals.headers)
+    }
+
+    /// The mutable headers of the error, which are allocated on the first call
+    pub fn headers_mut(&mut self) -> &mut http::HeaderMap {
+        &mut self.internals.get_or_insert_with(Default::default).headers
+    }
+}
+
+/// The errors are compared by their serialized fields, so a deserialized error equals the
+/// original one
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+            && self.links == other.links
+            && self.status == other.status
+            && self.code == other.code
+            && self.title == other.title
+            && self.detail == other.detail
+            && self.source == other.source
+            && self.meta == other.meta
+    }
 }
 
 impl fmt::Display for Error {
@@ -74,6 +143,159 @@ impl fmt::Display for Error {
     }
 }
 
+/// Multiple errors found in one request, like the ones of validation or batch operations
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+pub struct Errors(pub Vec<Error>);
+
+impl Errors {
+    /// The most generally applicable status of the errors: the status shared by all of them,
+    /// otherwise `500 Internal Server Error` if any of them is a server error, or
+    /// `400 Bad Request`
+    pub fn status(&self) -> http::StatusCode {
+        let statuses: Vec<http::StatusCode> = self
+            .0
+            .iter()
+            .map(|err| {
+                err.status
+                    .as_deref()
+                    .and_then(|status| status.parse().ok())
+                    .unwrap_or(http::StatusCode::BAD_REQUEST)
+            })
+            .collect();
+        match statuses.split_first() {
+            Some((first, rest)) if rest.iter().all(|status| status == first) => *first,
+            _ if statuses.iter().any(http::StatusCode::is_server_error) => {
+                http::StatusCode::INTERNAL_SERVER_ERROR
+            },
+            _ => http::StatusCode::BAD_REQUEST,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Error> { self.0.iter() }
+}
+
+impl From<Error> for Errors {
+    fn from(err: Error) -> Self { Self(vec![err]) }
+}
+
+impl From<Vec<Error>> for Errors {
+    fn from(errors: Vec<Error>) -> Self { Self(errors) }
+}
+
+impl IntoIterator for Errors {
+    type IntoIter = std::vec::IntoIter<Error>;
+    type Item = Error;
+
+    fn into_iter(self) -> Self::IntoIter { self.0.into_iter() }
+}
+
+impl fmt::Display for Errors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let errors: Vec<String> = self.0.iter().map(ToString::to_string).collect();
+        write!(f, "{}", errors.join("; "))
+    }
+}
+
+impl RabbitholeErrorKind {
+    /// The extra information helping the client to fix the request
+    pub fn meta(&self) -> Option<Meta> {
+        let (key, value) = match self {
+            RabbitholeErrorKind::InvalidPaginationType { supported, .. }
+            | RabbitholeErrorKind::InvalidFilterType { supported, .. } => {
+                ("supported", serde_json::json!(supported))
+            },
+            RabbitholeErrorKind::LackOfPaginationParams { params, .. } => {
+                ("required", serde_json::json!(params))
+            },
+            RabbitholeErrorKind::ServiceUnavailable { retry_after, .. } => {
+                ("retryAfter", serde_json::json!(retry_after))
+            },
+            _ => return None,
+        };
+        Some(vec![(key.to_string(), value)].into_iter().collect())
+    }
+
+    /// The headers responded along with the error
+    pub fn headers(&self) -> http::HeaderMap {
+        let mut headers = http::HeaderMap::new();
+        match self {
+            RabbitholeErrorKind::ServiceUnavailable { retry_after, .. } => {
+                headers.insert(http::header::RETRY_AFTER, (*retry_after).into());
+            },
+            RabbitholeErrorKind::MethodNotAllowed { allowed, .. } => {
+                if let Ok(allow) = http::HeaderValue::from_str(&allowed.join(", ")) {
+                    headers.insert(http::header::ALLOW, allow);
+                }
+            },
+            _ => {},
+        }
+        headers
+    }
+}
+
+impl std::error::Error for RabbitholeErrorKind {}
+
+impl From<RabbitholeErrorKind> for Error {
+    fn from(kind: RabbitholeErrorKind) -> Self { kind.into_error(None) }
+}
+
+impl From<RabbitholeErrorKind> for Errors {
+    fn from(kind: RabbitholeErrorKind) -> Self { Error::from(kind).into() }
+}
+
+/// Converts the parameters of the error constructors into the owned ones kept in
+/// `RabbitholeErrorKind`
+trait IntoParam<T> {
+    fn into_param(self) -> T;
+}
+
+impl IntoParam<String> for &str {
+    fn into_param(self) -> String { self.to_string() }
+}
+
+impl IntoParam<String> for String {
+    fn into_param(self) -> String { self }
+}
+
+impl IntoParam<String> for &serde_json::Error {
+    fn into_param(self) -> String { self.to_string() }
+}
+
+impl IntoParam<String> for &http::Error {
+    fn into_param(self) -> String { self.to_string() }
+}
+
+impl IntoParam<Vec<String>> for &[&str] {
+    fn into_param(self) -> Vec<String> { self.iter().map(ToString::to_string).collect() }
+}
+
+impl IntoParam<Vec<String>> for &[String] {
+    fn into_param(self) -> Vec<String> { self.to_vec() }
+}
+
+impl IntoParam<u64> for u64 {
+    fn into_param(self) -> u64 { self }
+}
+
+impl IntoParam<usize> for usize {
+    fn into_param(self) -> usize { self }
+}
+
+impl IntoParam<f64> for f64 {
+    fn into_param(self) -> f64 { self }
+}
+
+impl IntoParam<std::string::FromUtf8Error> for &std::string::FromUtf8Error {
+    fn into_param(self) -> std::string::FromUtf8Error { self.clone() }
+}
+
+impl IntoParam<std::str::Utf8Error> for &std::str::Utf8Error {
+    fn into_param(self) -> std::str::Utf8Error { *self }
+}
+
 /// Rabbithole Error Code:
 ///   1. Magic Word(0..4): Fixed "RBH-", to indicate User that this error is from Rabbithole Server,
 ///                        rather than an application-specific error
@@ -85,166 +307,349 @@ impl fmt::Display for Error {
 ///     4. "04:" Query Result
 ///     5. "99": Unimplemented features
 ///   3. Specific Code(5..6): Two digits to indicate the more info about the location, just as the `title` said
-macro_rules! rabbithole_errors_inner {
-    ( $(ty: $ty:ident, status: $status:expr, code: $code:expr, title: $title:expr, detail: $detail:expr, param: [$($param_arg:ident: $param_ty:ty,)*];)* ) => {
-        $(
-            #[allow(non_snake_case)]
-            pub fn $ty($($param_arg: $param_ty,)* error_source: Option<ErrorSource>) -> Error {
-                Self {
+macro_rules! rabbithole_errors {
+    ( $(ty: $ty:ident, status: $status:expr, code: $code:expr, title: $title:expr, detail: $detail:expr, param: [$($param_arg:ident: $param_ty:ty => $kind_ty:ty,)*];)* ) => {
+        /// The typed kinds of the errors of Rabbithole, carrying the parameters of each error,
+        /// which can be converted into the wire `Error`
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum RabbitholeErrorKind {
+            $(
+                $ty { $($param_arg: $kind_ty,)* },
+            )*
+        }
+
+        impl RabbitholeErrorKind {
+            pub fn status(&self) -> http::StatusCode {
+                match self {
+                    $(Self::$ty { .. } => $status,)*
+                }
+            }
+
+            pub fn code(&self) -> &'static str {
+                match self {
+                    $(Self::$ty { .. } => $code,)*
+                }
+            }
+
+            pub fn title(&self) -> &'static str {
+                match self {
+                    $(Self::$ty { .. } => $title,)*
+                }
+            }
+
+            pub fn into_error(self, error_source: Option<ErrorSource>) -> Error {
+                Error {
                     id: Some(uuid::Uuid::new_v4().to_string()),
-                    status: Some($status.as_str().into()),
-                    code: Some($code.into()),
-                    title: Some($title.into()),
-                    detail: Some(format!($detail, $($param_arg = $param_arg),*)),
-                    source: error_source.unwrap_or(Default::default()),
+                    status: Some(self.status().as_str().into()),
+                    code: Some(self.code().into()),
+                    title: Some(self.title().into()),
+                    detail: Some(self.to_string()),
+                    source: error_source.unwrap_or_default(),
+                    meta: self.meta(),
+                    internals: Some(Box::new(ErrorInternals {
+                        headers: self.headers(),
+                        kind: Some(self),
+                    })),
                     ..Default::default()
                 }
             }
-        )*
-    };
-}
+        }
+
+        impl fmt::Display for RabbitholeErrorKind {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $(Self::$ty { $($param_arg,)* } => write!(f, $detail, $($param_arg = $param_arg),*),)*
+                }
+            }
+        }
 
-macro_rules! rabbithole_errors {
-    ( $(ty: $ty:ident, status: $status:expr, code: $code:expr, title: $title:expr, detail: $detail:expr, param: [$($param_arg:ident: $param_ty:ty,)*];)* ) => {
         impl Error {
-            rabbithole_errors_inner!($(ty: $ty, status: $status, code: $code, title: $title, detail: $detail, param: [$($param_arg: $param_ty,)*];)*);
+            $(
+                #[allow(non_snake_case)]
+                pub fn $ty($($param_arg: $param_ty,)* error_source: Option<ErrorSource>) -> Error {
+                    RabbitholeErrorKind::$ty { $($param_arg: $param_arg.into_param(),)* }
+                        .into_error(error_source)
+                }
+            )*
         }
     };
 }
 
 rabbithole_errors! {
     ty: InvalidUtf8String,
-    status: http::StatusCode::NOT_ACCEPTABLE,
+    status: http::StatusCode::BAD_REQUEST,
     code: "RBH-0001",
     title: "Invalid UTF-8 String",
     detail: "Invalid UTF-8 String found: {err}",
-    param: [err: &std::string::FromUtf8Error,];
+    param: [err: &std::string::FromUtf8Error => std::string::FromUtf8Error,];
 
     ty: NotUtf8String,
-    status: http::StatusCode::NOT_ACCEPTABLE,
+    status: http::StatusCode::BAD_REQUEST,
     code: "RBH-0002",
     title: "Not an UTF-8 String",
     detail: "String `{invalid}` cannot be decoded as UTF-8 correctly: {err}",
-    param: [invalid: &str,  err: &std::str::Utf8Error,];
+    param: [invalid: &str => String, err: &std::str::Utf8Error => std::str::Utf8Error,];
 
     ty: InvalidJson,
-    status: http::StatusCode::NOT_ACCEPTABLE,
+    status: http::StatusCode::BAD_REQUEST,
     code: "RBH-0003",
     title: "Invalid JSON Content",
     detail: "An error found when parsing JSON: {invalid}",
-    param: [invalid: &serde_json::Error,];
+    param: [invalid: &serde_json::Error => String,];
 
     ty: RelationshipPathNotSupported,
     status: http::StatusCode::BAD_REQUEST,
     code: "RBH-0004",
     title: "Relationship Path Not Supported",
     detail: "The relationship path in Query `{relat_path}` is not supported yet",
-    param: [relat_path: &str,];
+    param: [relat_path: &str => String,];
 
     ty: InvalidUri,
     status: http::StatusCode::BAD_REQUEST,
     code: "RBH-0005",
     title: "Invalid URI",
-    detail: "Failed when parsing URI: {}",
-    param: [error: &http::Error,];
+    detail: "Failed when parsing URI: {error}",
+    param: [error: &http::Error => String,];
+
+    ty: InvalidRequestBody,
+    status: http::StatusCode::BAD_REQUEST,
+    code: "RBH-0006",
+    title: "Invalid Request Body",
+    detail: "The request body cannot be read: {error}",
+    param: [error: &str => String,];
+
+    ty: InvalidPathParameter,
+    status: http::StatusCode::NOT_FOUND,
+    code: "RBH-0007",
+    title: "Invalid Path Parameter",
+    detail: "The path parameters cannot be parsed: {error}",
+    param: [error: &str => String,];
+
+    ty: RouteNotFound,
+    status: http::StatusCode::NOT_FOUND,
+    code: "RBH-0008",
+    title: "Route Not Found",
+    detail: "No route matches the path `{path}`",
+    param: [path: &str => String,];
+
+    ty: MethodNotAllowed,
+    status: http::StatusCode::METHOD_NOT_ALLOWED,
+    code: "RBH-0009",
+    title: "Method Not Allowed",
+    detail: "The method `{method}` is not allowed on the path `{path}`, the allowed ones are: {allowed:?}",
+    param: [method: &str => String, path: &str => String, allowed: &[&str] => Vec<String>,];
+
+    ty: ServiceUnavailable,
+    status: http::StatusCode::SERVICE_UNAVAILABLE,
+    code: "RBH-0010",
+    title: "Service Unavailable",
+    detail: "The service is unavailable now: {reason}, please retry after {retry_after} seconds",
+    param: [reason: &str => String, retry_after: u64 => u64,];
 
     ty: InvalidPaginationType,
-    status: http::StatusCode::NOT_ACCEPTABLE,
+    status: http::StatusCode::BAD_REQUEST,
     code: "RBH-0101",
     title: "Invalid Pagination Type",
-    detail: r#"Invalid pagination type: {invalid}, the valid ones are: ["OffsetBased", "PageBased", "CursorBased"]"#,
-    param: [invalid: &str,];
+    detail: "Invalid pagination type: {invalid}, the valid ones are: {supported:?}",
+    param: [invalid: &str => String, supported: &[&str] => Vec<String>,];
 
     ty: InvalidFilterType,
-    status: http::StatusCode::NOT_ACCEPTABLE,
+    status: http::StatusCode::BAD_REQUEST,
     code: "RBH-0102",
     title: "Invalid Filter Type",
-    detail: r#"Invalid filter type: {invalid}, the valid ones are: ["Rsql"]"#,
-    param: [invalid: &str,];
+    detail: "Invalid filter type: {invalid}, the valid ones are: {supported:?}",
+    param: [invalid: &str => String, supported: &[&str] => Vec<String>,];
 
     ty: UnmatchedFilterItem,
-    status: http::StatusCode::NOT_ACCEPTABLE,
+    status: http::StatusCode::BAD_REQUEST,
     code: "RBH-0103",
     title: "Unmatched Filter Item",
     detail: "Filter type `[{filter_type}]` and filter item [{filter_key} = {filter_value}] are not matched",
-    param: [filter_type: &str, filter_key: &str, filter_value: &str,];
+    param: [filter_type: &str => String, filter_key: &str => String, filter_value: &str => String,];
 
     ty: InvalidCursorContent,
-    status: http::StatusCode::NOT_ACCEPTABLE,
+    status: http::StatusCode::BAD_REQUEST,
     code: "RBH-0104",
     title: "Invalid Cursor Content",
     detail: r#"The content of `page[cursor]` is not acceptable, please use a valid cursor"#,
     param: [];
 
     ty: BeforeAndAfterCursorNotMatch,
-    status: http::StatusCode::NOT_ACCEPTABLE,
+    status: http::StatusCode::BAD_REQUEST,
     code: "RBH-0105",
     title: "Bafore and After Cursor Not Match",
     detail: r#"The `page[before]` and `page[after]` cursor both exists, but the contents do not match. Maybe because the index of `before` cursor is smaller than `after` cursor"#,
     param: [];
 
     ty: LackOfPaginationParams,
-    status: http::StatusCode::NOT_ACCEPTABLE,
+    status: http::StatusCode::BAD_REQUEST,
     code: "RBH-0106",
     title: "Lack of Pagination Item",
     detail: "Pagination type `[{page_type}]` need ALL of the parameters: [{params:?}]",
-    param: [page_type: &str, params: &[&str],];
+    param: [page_type: &str => String, params: &[&str] => Vec<String>,];
 
     ty: UnsupportedRsqlComparison,
-    status: http::StatusCode::NOT_ACCEPTABLE,
+    status: http::StatusCode::BAD_REQUEST,
     code: "RBH-0107",
     title: "Unsupported RSQL Comparison",
     detail: "Comparison `{comparison:?}` with {param_cnt} parameter(s) is not supported now",
-    param: [comparison: &[String], param_cnt: usize,];
+    param: [comparison: &[String] => Vec<String>, param_cnt: usize => usize,];
 
     ty: InvalidPageSize,
-    status: http::StatusCode::NOT_ACCEPTABLE,
+    status: http::StatusCode::BAD_REQUEST,
     code: "RBH-0108",
     title: "Invalid Page Size",
     detail: "The page size should larger then zero",
     param: [];
 
+    ty: QueryFieldNotExist,
+    status: http::StatusCode::BAD_REQUEST,
+    code: "RBH-0109",
+    title: "Query Field Not Exist",
+    detail: "Field `{field}` in the query does not exist",
+    param: [field: &str => String,];
+
     ty: InvalidJsonApiVersion,
     status: http::StatusCode::NOT_ACCEPTABLE,
     code: "RBH-0201",
     title: "Invalid JSON API Version",
     detail: "A invalid JSON:API version: {invalid_version}",
-    param: [invalid_version: String,];
+    param: [invalid_version: String => String,];
+
+    ty: FieldNotWritable,
+    status: http::StatusCode::FORBIDDEN,
+    code: "RBH-0202",
+    title: "Field Not Writable",
+    detail: "Field `{field}` cannot be written",
+    param: [field: &str => String,];
+
+    ty: TypeNotMatch,
+    status: http::StatusCode::CONFLICT,
+    code: "RBH-0203",
+    title: "Type Not Match",
+    detail: "The type `{found}` in the body does not match the type `{expected}` of the endpoint",
+    param: [expected: &str => String, found: &str => String,];
+
+    ty: IdNotMatch,
+    status: http::StatusCode::CONFLICT,
+    code: "RBH-0204",
+    title: "ID Not Match",
+    detail: "The id `{found}` in the body does not match the id `{expected}` in the URL",
+    param: [expected: &str => String, found: &str => String,];
+
+    ty: ClientIdNotSupported,
+    status: http::StatusCode::FORBIDDEN,
+    code: "RBH-0205",
+    title: "Client-Generated ID Not Supported",
+    detail: "The id of `{ty}` cannot be generated by the client",
+    param: [ty: &str => String,];
+
+    ty: ClientIdRequired,
+    status: http::StatusCode::BAD_REQUEST,
+    code: "RBH-0206",
+    title: "Client-Generated ID Required",
+    detail: "The id of `{ty}` should be generated by the client",
+    param: [ty: &str => String,];
+
+    ty: DuplicateId,
+    status: http::StatusCode::CONFLICT,
+    code: "RBH-0207",
+    title: "Duplicate ID",
+    detail: "A resource of `{ty}` with id `{id}` already exists",
+    param: [ty: &str => String, id: &str => String,];
+
+    ty: MissingField,
+    status: http::StatusCode::BAD_REQUEST,
+    code: "RBH-0208",
+    title: "Missing Field",
+    detail: "Field `{field}` is missing in the body",
+    param: [field: &str => String,];
+
+    ty: InvalidFieldValue,
+    status: http::StatusCode::BAD_REQUEST,
+    code: "RBH-0209",
+    title: "Invalid Field Value",
+    detail: "Field `{field}` has an invalid value: {error}",
+    param: [field: &str => String, error: &str => String,];
+
+    ty: FieldRequired,
+    status: http::StatusCode::BAD_REQUEST,
+    code: "RBH-0210",
+    title: "Field Required",
+    detail: "Field `{field}` is required",
+    param: [field: &str => String,];
+
+    ty: InvalidFieldLength,
+    status: http::StatusCode::BAD_REQUEST,
+    code: "RBH-0211",
+    title: "Invalid Field Length",
+    detail: "The length of field `{field}` should be {expected}, but {found} found",
+    param: [field: &str => String, expected: &str => String, found: usize => usize,];
+
+    ty: FieldOutOfRange,
+    status: http::StatusCode::BAD_REQUEST,
+    code: "RBH-0212",
+    title: "Field Out of Range",
+    detail: "Field `{field}` should be {expected}, but {found} found",
+    param: [field: &str => String, expected: &str => String, found: f64 => f64,];
+
+    ty: FieldPatternNotMatch,
+    status: http::StatusCode::BAD_REQUEST,
+    code: "RBH-0213",
+    title: "Field Pattern Not Match",
+    detail: "Field `{field}` does not match the pattern `{pattern}`",
+    param: [field: &str => String, pattern: &str => String,];
 
     ty: InvalidContentType,
     status: http::StatusCode::UNSUPPORTED_MEDIA_TYPE,
     code: "RBH-0301",
     title: "Invalid Content-Type Header",
     detail: "The `Content-Type` header of Request must be {header_hint}, but {invalid_header} found",
-    param: [header_hint: &str, invalid_header: &str,];
+    param: [header_hint: &str => String, invalid_header: &str => String,];
 
     ty: InvalidAccept,
     status: http::StatusCode::NOT_ACCEPTABLE,
     code: "RBH-0302",
     title: "Invalid Accept Header",
     detail: "The `Accept` header of Request must be {header_hint}, but {invalid_header} found",
-    param: [header_hint: &str, invalid_header: &str,];
+    param: [header_hint: &str => String, invalid_header: &str => String,];
 
     ty: FieldNotExist,
     status: http::StatusCode::NOT_FOUND,
     code: "RBH-0401",
     title: "Field Not Exist",
     detail: "Field `{field}` does not exist",
-    param: [field: &str,];
+    param: [field: &str => String,];
 
     ty: FieldNotMatch,
-    status: http::StatusCode::NOT_FOUND,
+    status: http::StatusCode::BAD_REQUEST,
     code: "RBH-0402",
-    title: "Field Not Exist",
+    title: "Field Not Match",
     detail: "The type of `{field}` is not match: comparing `{slf}` and `{other}`",
-    param: [field: &str, slf: &str, other: &str,];
+    param: [field: &str => String, slf: &str => String, other: &str => String,];
+
+    ty: AccessForbidden,
+    status: http::StatusCode::FORBIDDEN,
+    code: "RBH-0403",
+    title: "Access Forbidden",
+    detail: "The operation `{operation}` on `{target}` is forbidden",
+    param: [operation: &str => String, target: &str => String,];
 
     ty: ParentResourceNotExist,
     status: http::StatusCode::NOT_FOUND,
     code: "RBH-0404",
     title: "Parent Resource of Relationship Not Exist",
     detail: "The parent resource of the relationship `{target_relat}` does not exist",
-    param: [target_relat: &str,];
+    param: [target_relat: &str => String,];
+
+    // `RBH-0404` was taken by `ParentResourceNotExist` before, so the missing resource itself
+    // is `RBH-0405` though responded as `404 Not Found`, and the codes are kept for the clients
+    ty: ResourceNotExist,
+    status: http::StatusCode::NOT_FOUND,
+    code: "RBH-0405",
+    title: "Resource Not Exist",
+    detail: "The resource `{target}` does not exist",
+    param: [target: &str => String,];
 
     ty: CursorPaginationNotImplemented,
     status: http::StatusCode::NOT_IMPLEMENTED,
@@ -272,5 +677,12 @@ rabbithole_errors! {
     code: "RBH-9904",
     title: "Operation is not Implemented",
     detail: "The operation `{operation}` is not implemented",
-    param: [operation: &str,];
+    param: [operation: &str => String,];
+
+    ty: LazyRelatedNotImplemented,
+    status: http::StatusCode::NOT_IMPLEMENTED,
+    code: "RBH-9905",
+    title: "Related Resources of Lazy Relationship are not Implemented",
+    detail: "The relationship `{field}` holds only the ids, please override `Fetching::fetch_related` to load its related resources",
+    param: [field: &str => String,];
 }
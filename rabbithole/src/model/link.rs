@@ -8,6 +8,12 @@ pub type Links = HashMap<String, Link>;
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct WrappedUri(#[serde(with = "http_serde::uri")] http::Uri);
 
+impl FromStr for WrappedUri {
+    type Err = http::uri::InvalidUri;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Ok(WrappedUri(s.parse()?)) }
+}
+
 impl FromStr for Link {
     type Err = http::uri::InvalidUri;
 
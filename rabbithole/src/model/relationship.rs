@@ -1,6 +1,7 @@
 use crate::model::link::{Link, Links};
 use crate::model::resource::IdentifierData;
 use crate::model::Meta;
+use serde::{Deserialize, Deserializer};
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::iter::FromIterator;
@@ -13,7 +14,10 @@ lazy_static! {
 /// Relationship with another object
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct Relationship {
-    pub data: IdentifierData,
+    /// The resource linkage, which is left out of the relationships rendered with only the links
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "linkage")]
+    pub data: Option<IdentifierData>,
     #[serde(skip_serializing_if = "RelationshipLinks::is_not_valid")]
     #[serde(default)]
     pub links: RelationshipLinks,
@@ -25,7 +29,7 @@ pub struct Relationship {
 impl Relationship {
     pub fn null(links: Links, meta: Meta) -> Relationship {
         Self {
-            data: IdentifierData::Single(None),
+            data: Some(IdentifierData::Single(None)),
             links: links.into(),
             meta,
         }
@@ -40,6 +44,23 @@ impl Relationship {
     }
 }
 
+/// A present `data` is the linkage even if it is `null`, which is an empty to-one relationship
+fn linkage<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<IdentifierData>, D::Error> {
+    IdentifierData::deserialize(deserializer).map(Some)
+}
+
+/// When the `data` of a relationship is rendered, decorated by `linkage = ".."` of
+/// `#[entity(to_one(..))]` and `#[entity(to_many(..))]`
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Linkage {
+    Always,
+    /// Only when the relationship is in the `include` query of the primary data, or there is no
+    /// `include` query at all
+    WhenIncluded,
+    /// Never, leaving only the links, like for the large to-many relationships
+    Never,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct RelationshipLinks {
     #[serde(rename = "self")]
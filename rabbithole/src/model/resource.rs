@@ -130,10 +130,17 @@ impl Attributes {
 
     pub fn is_empty(&self) -> bool { self.0.is_empty() }
 
+    pub fn keys(&self) -> impl Iterator<Item = &String> { self.0.keys() }
+
     pub fn retain(mut self, keys: &HashSet<String>) -> Self {
         self.0.retain(|k, _| keys.contains(k));
         self
     }
+
+    pub fn retain_by<F: FnMut(&str) -> bool>(mut self, mut f: F) -> Self {
+        self.0.retain(|k, _| f(k));
+        self
+    }
 }
 
 /// Valid Resource Identifier (can be None)
@@ -1,17 +1,23 @@
-use crate::entity::SingleEntity;
+use crate::entity::{Patchable, SingleEntity};
 use crate::model::document::Document;
 use crate::model::link::Links;
 use crate::model::relationship::Relationship;
 use crate::model::resource::{IdentifierData, Resource};
 use crate::model::{error, Meta};
+use crate::policy::AllowAll;
 use crate::query::Query;
-use crate::Result;
 use async_trait::async_trait;
 
-pub type OperationResult<T> = Result<OperationResultData<T>>;
-pub type CollectionResult<T> = Result<OperationResultData<Vec<T>>>;
-pub type SingleResult<T> = Result<OperationResultData<Option<T>>>;
-pub type UpdateResult<T> = Result<OperationResultData<(String, Option<T>)>>;
+/// The operations can fail with multiple errors at once, and a single `error::Error` can be
+/// converted with `?` or `into()`
+pub type OperationErrorsResult<T> = Result<T, error::Errors>;
+pub type OperationResult<T> = OperationErrorsResult<OperationResultData<T>>;
+pub type CollectionResult<T> = OperationErrorsResult<OperationResultData<Vec<T>>>;
+pub type SingleResult<T> = OperationErrorsResult<OperationResultData<Option<T>>>;
+pub type UpdateResult<T> = OperationErrorsResult<OperationResultData<(String, Option<T>)>>;
+pub type CreateResult<T> = OperationErrorsResult<OperationResultData<Created<T>>>;
+/// The id of the items of the operation `T`, parsed from the routes
+pub type ItemId<T> = <<T as Operation>::Item as SingleEntity>::Id;
 
 pub trait Operation {
     type Item: SingleEntity + Send + Sync;
@@ -41,40 +47,64 @@ pub trait Fetching: Operation {
     async fn fetch_collection(
         &self, uri: &str, path: &http::Uri, query: &Query,
     ) -> CollectionResult<Self::Item> {
-        Err(error::Error::OperationNotImplemented(
-            "fetch_collection",
-            None,
-        ))
+        Err(error::Error::OperationNotImplemented("fetch_collection", None).into())
     }
     /// Mapping to `/<ty>/<id>?<query>`
     #[allow(unused_variables)]
     async fn fetch_single(
-        &self, id: &str, uri: &str, path: &http::Uri, query: &Query,
+        &self, id: &ItemId<Self>, uri: &str, path: &http::Uri, query: &Query,
     ) -> SingleResult<Self::Item> {
-        Err(error::Error::OperationNotImplemented("fetch_single", None))
+        Err(error::Error::OperationNotImplemented("fetch_single", None).into())
     }
     /// Mapping to `/<ty>/<id>/relationships/<related_field>?<query>`
-    #[allow(unused_variables)]
+    ///
+    /// By default, the relationship is picked from the item loaded by `fetch_single`, where
+    /// neither the sparse fieldsets nor the linkage of the relationship are applied
     async fn fetch_relationship(
-        &self, id: &str, related_field: &str, uri: &str, path: &http::Uri, query: &Query,
+        &self, id: &ItemId<Self>, related_field: &str, uri: &str, path: &http::Uri, query: &Query,
     ) -> OperationResult<Relationship> {
-        Err(error::Error::OperationNotImplemented(
-            "fetch_relationship",
-            None,
-        ))
+        let item = fetch_parent(self, id, related_field, uri, path, query).await?;
+        let relat = item
+            .relationships(uri)
+            .remove(related_field)
+            .ok_or_else(|| error::Error::FieldNotExist(related_field, None))?;
+        Ok(OperationResultData {
+            data: relat,
+            ..Default::default()
+        })
     }
     /// Mapping to `/<ty>/<id>/<related_field>?<query>`
-    /// # Returns
-    /// Because `rabbithole` can only get the field by String, so it cannot get the actual type, so you should return
-    /// the document yourself
-    #[allow(unused_variables)]
+    ///
+    /// By default, the document is built by `SingleEntity::related_document` of the item loaded by
+    /// `fetch_single`, so the services holding only the ids of the related resources should
+    /// override it, where the `related_field` can be parsed into the generated
+    /// `<Entity>Relationship` enum to handle each relationship separately
     async fn fetch_related(
-        &self, id: &str, related_field: &str, uri: &str, path: &http::Uri, query: &Query,
-    ) -> Result<Document> {
-        Err(error::Error::OperationNotImplemented("fetch_related", None))
+        &self, id: &ItemId<Self>, related_field: &str, uri: &str, path: &http::Uri, query: &Query,
+    ) -> OperationErrorsResult<Document> {
+        let item = fetch_parent(self, id, related_field, uri, path, query).await?;
+        item.related_document(related_field, uri, query, path.clone())
+            .map_err(Into::into)
     }
 }
 
+/// The parent item of the relationship `related_field` loaded by `fetch_single`, where a missing
+/// one is `ParentResourceNotExist`, and a relationship hidden by the `FieldVisibility` of the
+/// query is `FieldNotExist`
+async fn fetch_parent<T: Fetching + Sync + ?Sized>(
+    service: &T, id: &ItemId<T>, related_field: &str, uri: &str, path: &http::Uri, query: &Query,
+) -> OperationErrorsResult<T::Item> {
+    let ty = <T::Item as SingleEntity>::ty();
+    if !query.visibility().can_read_field(&ty, related_field) {
+        return Err(error::Error::FieldNotExist(related_field, None).into());
+    }
+    service
+        .fetch_single(id, uri, path, query)
+        .await?
+        .data
+        .ok_or_else(|| error::Error::ParentResourceNotExist(related_field, None).into())
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct ResourceDataWrapper {
     pub data: Resource,
@@ -87,7 +117,7 @@ impl ResourceDataWrapper {
     {
         entities
             .iter()
-            .filter_map(|d| d.to_resource(&path, &Default::default()))
+            .filter_map(|d| d.to_resource(&path, &Default::default(), &AllowAll))
             .map(|data| Self { data })
             .collect()
     }
@@ -98,90 +128,105 @@ pub struct IdentifierDataWrapper {
     pub data: IdentifierData,
 }
 
+/// The outcome of `Creating::create`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Created<T> {
+    /// Mapped to `201 Created` with the created resource and a `Location` header pointing to it
+    Resource(T),
+    /// Mapped to `202 Accepted`, when the creation is going to be processed asynchronously
+    Accepted,
+    /// Mapped to `204 No Content`, when the resource is created exactly as the client requested,
+    /// including the client-generated id
+    #[default]
+    NoContent,
+}
+
+impl<T> From<Option<T>> for Created<T> {
+    fn from(item: Option<T>) -> Self { item.map_or(Created::NoContent, Created::Resource) }
+}
+
 #[async_trait]
 pub trait Creating: Operation {
     /// Mapping to `POST /<ty>`
     /// # Returns
     ///
-    /// If returns `Ok(Some(item))`, then will be mapped to `StatusCode == '201 Created'` with created Resource;
-    /// If returns `Ok(None)`, then will be mapped to `StatusCode == '204 No Content'` with empty body
+    /// If returns `Created::Resource(item)`, then will be mapped to `StatusCode == '201 Created'` with created Resource and the `Location` header;
+    /// If returns `Created::Accepted`, then will be mapped to `StatusCode == '202 Accepted'`;
+    /// If returns `Created::NoContent`, then will be mapped to `StatusCode == '204 No Content'` with empty body
     #[allow(unused_variables)]
     async fn create(
         &mut self, data: &ResourceDataWrapper, uri: &str, path: &http::Uri,
-    ) -> SingleResult<Self::Item> {
-        Err(error::Error::OperationNotImplemented("create", None))
+    ) -> CreateResult<Self::Item> {
+        Err(error::Error::OperationNotImplemented("create", None).into())
     }
 }
 
 #[async_trait]
 pub trait Updating: Operation {
     /// Mapping to `PATCH /<ty>/<id>`
+    /// # Arguments
+    ///
+    /// * `patch` - The fields present in the request body, which can be applied with `Patchable::apply_patch`.
+    ///   The missing fields are `None` and should be left untouched
+    ///
     /// # Returns
     ///
     /// If the result matches the incoming data, or in other words, the result matches the expectation of the user, then should return `None`, which will be mapped as `204 No Content`
     /// Otherwise, this function should return `200 OK`, with the whole updated resource
     #[allow(unused_variables)]
     async fn update_resource(
-        &mut self, id: &str, data: &ResourceDataWrapper, uri: &str, path: &http::Uri,
-    ) -> SingleResult<Self::Item> {
-        Err(error::Error::OperationNotImplemented(
-            "update_resource",
-            None,
-        ))
+        &mut self, id: &ItemId<Self>, patch: <Self::Item as Patchable>::Patch, uri: &str,
+        path: &http::Uri,
+    ) -> SingleResult<Self::Item>
+    where
+        Self::Item: Patchable,
+    {
+        Err(error::Error::OperationNotImplemented("update_resource", None).into())
     }
     /// Mapping to `PATCH /<ty>/<id>/relationships/<field>`
     /// # Arguments
     ///
-    /// * `id_field` - The first string is the id of the resource, the second string is the field name of the relationship
+    /// * `id_field` - The first one is the parsed id of the resource, the second string is the field name of the relationship
     ///
     /// # Returns
     ///
     /// * A tuple of the updated result. The first string is the field name(should be equal with the second string of `id_field`)
     #[allow(unused_variables)]
     async fn replace_relationship(
-        &mut self, id_field: &(String, String), data: &IdentifierDataWrapper, uri: &str,
+        &mut self, id_field: &(ItemId<Self>, String), data: &IdentifierDataWrapper, uri: &str,
         path: &http::Uri,
     ) -> UpdateResult<Self::Item> {
-        Err(error::Error::OperationNotImplemented(
-            "replace_relationship",
-            None,
-        ))
+        Err(error::Error::OperationNotImplemented("replace_relationship", None).into())
     }
     /// Mapping to `POST /<ty>/<id>/relationships/<field>`
     /// # Arguments
     ///
-    /// * `id_field` - The first string is the id of the resource, the second string is the field name of the relationship
+    /// * `id_field` - The first one is the parsed id of the resource, the second string is the field name of the relationship
     ///
     /// # Returns
     ///
     /// * A tuple of the updated result. The first string is the field name(should be equal with the second string of `id_field`)
     #[allow(unused_variables)]
     async fn add_relationship(
-        &mut self, id_field: &(String, String), data: &IdentifierDataWrapper, uri: &str,
+        &mut self, id_field: &(ItemId<Self>, String), data: &IdentifierDataWrapper, uri: &str,
         path: &http::Uri,
     ) -> UpdateResult<Self::Item> {
-        Err(error::Error::OperationNotImplemented(
-            "add_relationship",
-            None,
-        ))
+        Err(error::Error::OperationNotImplemented("add_relationship", None).into())
     }
     /// Mapping to `DELETE /<ty>/<id>/relationships/<field>`
     /// # Arguments
     ///
-    /// * `id_field` - The first string is the id of the resource, the second string is the field name of the relationship
+    /// * `id_field` - The first one is the parsed id of the resource, the second string is the field name of the relationship
     ///
     /// # Returns
     ///
     /// * A tuple of the updated result. The first string is the field name(should be equal with the second string of `id_field`)
     #[allow(unused_variables)]
     async fn remove_relationship(
-        &mut self, id_field: &(String, String), data: &IdentifierDataWrapper, uri: &str,
+        &mut self, id_field: &(ItemId<Self>, String), data: &IdentifierDataWrapper, uri: &str,
         path: &http::Uri,
     ) -> UpdateResult<Self::Item> {
-        Err(error::Error::OperationNotImplemented(
-            "remove_relationship",
-            None,
-        ))
+        Err(error::Error::OperationNotImplemented("remove_relationship", None).into())
     }
 }
 
@@ -190,11 +235,8 @@ pub trait Deleting: Operation {
     /// Mapping to `DELETE /<ty>/<id>`
     #[allow(unused_variables)]
     async fn delete_resource(
-        &mut self, id: &str, uri: &str, path: &http::Uri,
+        &mut self, id: &ItemId<Self>, uri: &str, path: &http::Uri,
     ) -> OperationResult<()> {
-        Err(error::Error::OperationNotImplemented(
-            "delete_resource",
-            None,
-        ))
+        Err(error::Error::OperationNotImplemented("delete_resource", None).into())
     }
 }
@@ -0,0 +1,266 @@
+use crate::model::document::{Document, DocumentItem, PrimaryDataItem};
+use crate::model::error;
+use crate::model::error::ErrorSource;
+use crate::model::relationship::Relationship;
+use crate::model::resource::{IdentifierData, Resource, ResourceIdentifier};
+use crate::Result;
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+/// The operation an endpoint is going to invoke on a service
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Action {
+    FetchCollection,
+    FetchSingle,
+    FetchRelationship,
+    FetchRelated,
+    Create,
+    UpdateResource,
+    ReplaceRelationship,
+    AddRelationship,
+    RemoveRelationship,
+    DeleteResource,
+}
+
+impl Action {
+    /// The name of the `Operation` method this action is mapped to
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Action::FetchCollection => "fetch_collection",
+            Action::FetchSingle => "fetch_single",
+            Action::FetchRelationship => "fetch_relationship",
+            Action::FetchRelated => "fetch_related",
+            Action::Create => "create",
+            Action::UpdateResource => "update_resource",
+            Action::ReplaceRelationship => "replace_relationship",
+            Action::AddRelationship => "add_relationship",
+            Action::RemoveRelationship => "remove_relationship",
+            Action::DeleteResource => "delete_resource",
+        }
+    }
+}
+
+/// How a denied request is reported to the client
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Denial {
+    /// Mapped to `403 Forbidden`
+    Forbidden,
+    /// Mapped to `404 Not Found`, hiding the existence of the resource from the client
+    NotFound,
+}
+
+impl Denial {
+    pub fn into_error(self, ctx: &PolicyContext) -> error::Error {
+        let target = match &ctx.id {
+            Some(id) => format!("{}/{}", ctx.ty, id),
+            None => ctx.ty.clone(),
+        };
+        match self {
+            Denial::Forbidden => error::Error::AccessForbidden(ctx.action.as_str(), &target, None),
+            Denial::NotFound => error::Error::ResourceNotExist(&target, None),
+        }
+    }
+}
+
+/// The request a `Policy` is asked about
+#[derive(Debug, Clone)]
+pub struct PolicyContext {
+    pub action: Action,
+    /// The type of the resource the route is mapped to
+    pub ty: String,
+    /// The id in the route, if any
+    pub id: Option<String>,
+    /// The relationship field in the route, if any
+    pub related_field: Option<String>,
+    /// The headers of the request, where the credentials of the client usually are
+    pub headers: http::HeaderMap,
+}
+
+/// Access control consulted by the endpoints before and after invoking an `Operation`
+///
+/// A `Policy` only sees the `ty` and `id` of the route and the rendered `Resource`s, so one
+/// implementation can guard all the services
+#[async_trait]
+pub trait Policy: Send + Sync {
+    /// Checked before the `Operation` method mapped to `ctx.action` is invoked
+    #[allow(unused_variables)]
+    async fn authorize(&self, ctx: &PolicyContext) -> std::result::Result<(), Denial> { Ok(()) }
+
+    /// The fields the client of `ctx` can read and write, `None` means all of them
+    #[allow(unused_variables)]
+    fn field_visibility(&self, ctx: &PolicyContext) -> Option<Arc<dyn FieldVisibility>> { None }
+
+    /// Checked for every resource in the primary data and the `included` of a response document
+    #[allow(unused_variables)]
+    fn can_read(
+        &self, ctx: &PolicyContext, resource: &Resource,
+    ) -> std::result::Result<(), Denial> {
+        Ok(())
+    }
+
+    /// Checked for every resource identifier in the relationship linkage of a response, where the
+    /// resources themselves may not be rendered at all
+    #[allow(unused_variables)]
+    fn can_read_identifier(
+        &self, ctx: &PolicyContext, id: &ResourceIdentifier,
+    ) -> std::result::Result<(), Denial> {
+        Ok(())
+    }
+
+    /// Removes the resources which cannot be read from `doc`
+    ///
+    /// Denied resources are silently dropped from collections and `included`, while a denied
+    /// single primary resource makes the whole document an error
+    fn filter_document(&self, ctx: &PolicyContext, mut doc: Document) -> Result<Document> {
+        let mut denied = HashSet::new();
+        if let DocumentItem::PrimaryData(Some((ref mut data, _))) = doc.item {
+            match data {
+                PrimaryDataItem::Single(resource) => {
+                    if let Err(denial) = self.can_read(ctx, resource) {
+                        return Err(denial.into_error(ctx));
+                    }
+                },
+                PrimaryDataItem::Multiple(resources) => {
+                    resources.retain(|r| {
+                        let readable = self.can_read(ctx, r).is_ok();
+                        if !readable {
+                            denied.insert(r.id.clone());
+                        }
+                        readable
+                    });
+                },
+            }
+        }
+        Ok(filter_included(self, ctx, doc, denied))
+    }
+
+    /// Removes the `included` resources which cannot be read from `doc`, leaving the primary data
+    /// as it is, like the one of a mutation response which the client has just written
+    ///
+    /// The identifiers of the denied resources are removed from the relationship linkage as well
+    fn filter_included(&self, ctx: &PolicyContext, doc: Document) -> Document {
+        filter_included(self, ctx, doc, Default::default())
+    }
+
+    /// Removes the identifiers which cannot be read from the linkage of `relationship`
+    fn filter_relationship(
+        &self, ctx: &PolicyContext, mut relationship: Relationship,
+    ) -> Relationship {
+        if let Some(data) = &mut relationship.data {
+            retain_identifiers(data, |id| self.can_read_identifier(ctx, id).is_ok());
+        }
+        relationship
+    }
+}
+
+fn filter_included<P: Policy + ?Sized>(
+    policy: &P, ctx: &PolicyContext, mut doc: Document, mut denied: HashSet<ResourceIdentifier>,
+) -> Document {
+    if let DocumentItem::PrimaryData(Some((ref mut data, ref mut included))) = doc.item {
+        included.retain(|id, r| {
+            let readable = policy.can_read(ctx, r).is_ok();
+            if !readable {
+                denied.insert(id.clone());
+            }
+            readable
+        });
+        let readable = |id: &ResourceIdentifier| {
+            !denied.contains(id) && policy.can_read_identifier(ctx, id).is_ok()
+        };
+        let resources: Vec<&mut Resource> = match data {
+            PrimaryDataItem::Single(resource) => vec![resource.as_mut()],
+            PrimaryDataItem::Multiple(resources) => resources.iter_mut().collect(),
+        };
+        for resource in resources.into_iter().chain(included.values_mut()) {
+            for relat in resource.relationships.values_mut() {
+                if let Some(data) = &mut relat.data {
+                    retain_identifiers(data, readable);
+                }
+            }
+        }
+    }
+    doc
+}
+
+fn retain_identifiers<F: Fn(&ResourceIdentifier) -> bool>(data: &mut IdentifierData, readable: F) {
+    match data {
+        IdentifierData::Single(id) => {
+            if id.as_ref().is_some_and(|id| !readable(id)) {
+                *id = None;
+            }
+        },
+        IdentifierData::Multiple(ids) => ids.retain(|id| readable(id)),
+    }
+}
+
+impl fmt::Debug for dyn Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "Policy") }
+}
+
+/// A `Policy` bound to the request of a client, which is carried by `Query` so that the
+/// collections can be filtered before being paged
+#[derive(Debug, Clone)]
+pub struct BoundPolicy {
+    pub policy: Arc<dyn Policy>,
+    pub ctx: PolicyContext,
+}
+
+impl BoundPolicy {
+    pub fn can_read(&self, resource: &Resource) -> bool {
+        self.policy.can_read(&self.ctx, resource).is_ok()
+    }
+}
+
+/// Field-level permissions of a client
+///
+/// Fields which cannot be read are stripped from `attributes` and `relationships` when rendering
+/// the resources, just like the ones not in `FieldsQuery`, while request bodies setting fields
+/// which cannot be written are rejected
+pub trait FieldVisibility: Send + Sync {
+    #[allow(unused_variables)]
+    fn can_read_field(&self, ty: &str, field: &str) -> bool { true }
+
+    #[allow(unused_variables)]
+    fn can_write_field(&self, ty: &str, field: &str) -> bool { true }
+
+    /// Checks all the `attributes` and `relationships` in a request body of resource `ty`
+    fn check_writable(&self, ty: &str, resource: &Resource) -> Result<()> {
+        for field in resource.attributes.keys() {
+            if !self.can_write_field(ty, field) {
+                return Err(error::Error::FieldNotWritable(
+                    field,
+                    Some(ErrorSource::from_pointer(&format!(
+                        "/data/attributes/{}",
+                        field
+                    ))),
+                ));
+            }
+        }
+        for field in resource.relationships.keys() {
+            if !self.can_write_field(ty, field) {
+                return Err(error::Error::FieldNotWritable(
+                    field,
+                    Some(ErrorSource::from_pointer(&format!(
+                        "/data/relationships/{}",
+                        field
+                    ))),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for dyn FieldVisibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "FieldVisibility") }
+}
+
+/// The default `Policy`, allowing everything
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AllowAll;
+
+impl Policy for AllowAll {}
+
+impl FieldVisibility for AllowAll {}
@@ -1,6 +1,7 @@
 use crate::entity::SingleEntity;
 use crate::model::error;
-use crate::query::FilterSettings;
+use crate::model::error::ErrorSource;
+use crate::query::{at_parameter, FilterSettings};
 use crate::Result;
 use itertools::Itertools;
 use rsql::parser::rsql::RsqlParser;
@@ -37,12 +38,21 @@ impl FilterData for RsqlFilterData {
     fn new(params: &HashMap<String, String>) -> Result<Self> {
         let mut res: HashMap<String, Expr> = Default::default();
         for (k, v) in params.iter() {
+            let parameter = format!("filter[{}]", k);
             if k.contains('.') {
-                return Err(error::Error::RelationshipPathNotSupported(&k, None));
+                return Err(error::Error::RelationshipPathNotSupported(
+                    &k,
+                    Some(ErrorSource::from_parameter(&parameter)),
+                ));
             }
-            let expr = RsqlParser::default()
-                .parse_to_node(v)
-                .map_err(|_| error::Error::UnmatchedFilterItem("Rsql", &k, &v, None))?;
+            let expr = RsqlParser::default().parse_to_node(v).map_err(|_| {
+                error::Error::UnmatchedFilterItem(
+                    "Rsql",
+                    &k,
+                    &v,
+                    Some(ErrorSource::from_parameter(&parameter)),
+                )
+            })?;
             res.insert(k.clone(), expr);
         }
         Ok(RsqlFilterData(res))
@@ -50,6 +60,7 @@ impl FilterData for RsqlFilterData {
 
     fn filter<E: SingleEntity>(&self, mut entities: Vec<E>) -> Result<Vec<E>> {
         for (ty_or_relat, expr) in &self.0 {
+            let parameter = format!("filter[{}]", ty_or_relat);
             entities = entities
                 .into_iter()
                 .filter_map(|r| {
@@ -65,7 +76,8 @@ impl FilterData for RsqlFilterData {
                         },
                     }
                 })
-                .collect::<Result<Vec<E>>>()?;
+                .collect::<Result<Vec<E>>>()
+                .map_err(at_parameter(&parameter))?;
         }
         Ok(entities)
     }
@@ -124,7 +136,7 @@ impl RsqlFilterData {
                         ));
                     }
                 } else {
-                    return Err(error::Error::FieldNotExist(&selector, None));
+                    return Err(error::Error::QueryFieldNotExist(&selector, None));
                 }
             },
             Expr::Node(op, left, right) => {
@@ -157,11 +169,18 @@ impl Default for FilterQuery {
 }
 
 impl FilterQuery {
+    const RSQL: &'static str = "Rsql";
+    /// The filter types which the `ty` of `FilterSettings` can be
+    pub const TYPES: &'static [&'static str] = &[Self::RSQL];
+
     pub fn new(settings: &FilterSettings, params: &HashMap<String, String>) -> Result<FilterQuery> {
-        if &settings.ty == "Rsql" {
-            RsqlFilterData::new(params).map(FilterQuery::Rsql)
-        } else {
-            Err(error::Error::InvalidFilterType(&settings.ty, None))
+        match settings.ty.as_str() {
+            Self::RSQL => RsqlFilterData::new(params).map(FilterQuery::Rsql),
+            _ => Err(error::Error::InvalidFilterType(
+                &settings.ty,
+                Self::TYPES,
+                None,
+            )),
         }
     }
 
@@ -12,12 +12,14 @@ use crate::query::sort::{OrderType, SortQuery};
 
 use crate::entity::SingleEntity;
 use crate::model::link::{Link, Links};
+use crate::policy::{AllowAll, BoundPolicy, FieldVisibility};
 use itertools::Itertools;
 use percent_encoding::percent_decode_str;
 use percent_encoding::{percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use regex::Regex;
 
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 pub type IncludeQuery = HashSet<String>;
 pub type FieldsQuery = HashMap<String, HashSet<String>>;
@@ -88,14 +90,46 @@ pub struct Query {
     pub sort: SortQuery,
     pub page: Option<PageQuery>,
     pub filter: FilterQuery,
+    /// The field-level permissions of the client, `None` means all fields are visible
+    pub visibility: Option<Arc<dyn FieldVisibility>>,
+    /// The `Policy` of the client, which drops the resources that cannot be read in
+    /// `Query::query` before paging, `None` means all resources are readable
+    pub policy: Option<BoundPolicy>,
 }
 
 impl Query {
+    pub fn visibility(&self) -> &dyn FieldVisibility {
+        self.visibility.as_deref().unwrap_or(&AllowAll)
+    }
+
+    /// The query whose `include` paths are cut to their first relationships, like `dogs` of
+    /// `dogs.fleas`, leaving the nested ones to `IncludeLoaders`, and without the `fields`, which
+    /// `IncludeLoaders` applies after the paths are resolved
+    pub fn shallow_include(&self) -> Query {
+        Query {
+            fields: Default::default(),
+            include: self.include.as_ref().map(|include| {
+                include
+                    .iter()
+                    .filter_map(|path| path.split('.').next())
+                    .map(ToString::to_string)
+                    .collect()
+            }),
+            ..self.clone()
+        }
+    }
+
     pub fn query<E: SingleEntity>(
         &self, mut data: Vec<E>, uri: &str, path: &http::Uri,
     ) -> Result<(Vec<E>, Links)> {
-        self.sort.sort(&mut data);
-        let data = self.filter.filter(data)?;
+        self.sort.sort(&mut data)?;
+        let mut data = self.filter.filter(data)?;
+        if let Some(policy) = &self.policy {
+            data.retain(|item| {
+                item.to_resource(uri, &Default::default(), self.visibility())
+                    .is_some_and(|resource| policy.can_read(&resource))
+            });
+        }
         let (data, relat_pages) = if let Some(page) = &self.page {
             page.page(&data)?
         } else {
@@ -214,7 +248,7 @@ impl QuerySettings {
                 }
 
                 if key == "sort" {
-                    sort_query.insert_raw(value)?;
+                    sort_query.insert_raw(value).map_err(at_parameter("sort"))?;
                     continue;
                 }
 
@@ -252,11 +286,11 @@ impl QuerySettings {
         };
         let sort = sort_query;
         let page = if let Some(_page_settings) = self.page.as_ref() {
-            Some(PageQuery::new(&self, &page_map)?)
+            Some(PageQuery::new(&self, &page_map).map_err(at_parameter("page"))?)
         } else {
             None
         };
-        let filter = FilterQuery::new(&self.filter, &filter_map)?;
+        let filter = FilterQuery::new(&self.filter, &filter_map).map_err(at_parameter("filter"))?;
         let query = Query {
             settings: self.clone(),
             include,
@@ -264,12 +298,24 @@ impl QuerySettings {
             sort,
             page,
             filter,
+            visibility: None,
+            policy: None,
         };
 
         Ok(query)
     }
 }
 
+/// Locates the error at the query `parameter`, unless it has been located more precisely
+pub(crate) fn at_parameter(parameter: &str) -> impl FnOnce(error::Error) -> error::Error + '_ {
+    move |mut err| {
+        if err.source.parameter.is_none() {
+            err.source.parameter = Some(parameter.to_string());
+        }
+        err
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::query::{PageSettings, QuerySettings, CHAR_SET};
@@ -1,6 +1,7 @@
 use crate::entity::SingleEntity;
 use crate::model::error;
-use crate::query::QuerySettings;
+use crate::model::error::ErrorSource;
+use crate::query::{at_parameter, QuerySettings};
 use crate::Result;
 use itertools::Itertools;
 use num_integer::Integer;
@@ -88,12 +89,20 @@ impl ToString for CursorBasedData {
 impl CursorBasedData {
     pub fn new(settings: &QuerySettings, params: &HashMap<String, String>) -> Result<Self> {
         let after = if let Some(after) = params.get("after") {
-            Some(after.parse::<Cursor>()?)
+            Some(
+                after
+                    .parse::<Cursor>()
+                    .map_err(at_parameter("page[after]"))?,
+            )
         } else {
             None
         };
         let before = if let Some(before) = params.get("before") {
-            Some(before.parse::<Cursor>()?)
+            Some(
+                before
+                    .parse::<Cursor>()
+                    .map_err(at_parameter("page[before]"))?,
+            )
         } else {
             None
         };
@@ -143,7 +152,9 @@ impl PageData for CursorBasedData {
 
         let (from, to) = match (after_opt, before_opt) {
             (Some(after), Some(before)) if after >= before => {
-                return Err(error::Error::BeforeAndAfterCursorNotMatch(None));
+                return Err(error::Error::BeforeAndAfterCursorNotMatch(Some(
+                    ErrorSource::from_parameter("page[before]"),
+                )));
             },
             // When the gap between `after` and `before` is larger than `size`
             (Some(after), Some(before)) if before - after > self.size + 1 => {
@@ -273,14 +284,16 @@ impl From<PageBasedData> for PageQuery {
 impl PageData for PageBasedData {
     fn page<E: SingleEntity>(&self, entities: &[E]) -> Result<(usize, usize, RelativePages<Self>)> {
         if self.size == 0 {
-            return Err(error::Error::InvalidPageSize(None));
+            return Err(error::Error::InvalidPageSize(Some(
+                ErrorSource::from_parameter("page[size]"),
+            )));
         }
 
         let start = (self.number * self.size).min(entities.len());
         let end = ((self.number + 1) * self.size).min(entities.len());
 
         let max_page =
-            Step::backward_checked(entities.len().div_ceil(&self.size), 1).unwrap_or_default();
+            Step::backward_checked(Integer::div_ceil(&entities.len(), &self.size), 1).unwrap_or_default();
 
         let first = Some(PageBasedData {
             number: 0,
@@ -326,16 +339,23 @@ impl ToString for PageQuery {
 }
 
 impl PageQuery {
+    const CURSOR_BASED: &'static str = "CursorBased";
+    const OFFSET_BASED: &'static str = "OffsetBased";
+    const PAGE_BASED: &'static str = "PageBased";
+    /// The pagination types which the `ty` of the page settings can be
+    pub const TYPES: &'static [&'static str] =
+        &[Self::OFFSET_BASED, Self::PAGE_BASED, Self::CURSOR_BASED];
+
     pub fn new(settings: &QuerySettings, params: &HashMap<String, String>) -> Result<PageQuery> {
         if let Some(page_settings) = settings.page.as_ref() {
             match page_settings.ty.as_str() {
-                "OffsetBased" => Ok(Self::OffsetBased(OffsetBasedData::new(&settings, params)?)),
-                "PageBased" => Ok(Self::PageBased(PageBasedData::new(&settings, params)?)),
-                "CursorBased" => Ok(Self::CursorBased(CursorBasedData::new(&settings, params)?)),
-                _ => Err(error::Error::InvalidPaginationType(&page_settings.ty, None)),
+                Self::OFFSET_BASED => Ok(Self::OffsetBased(OffsetBasedData::new(&settings, params)?)),
+                Self::PAGE_BASED => Ok(Self::PageBased(PageBasedData::new(&settings, params)?)),
+                Self::CURSOR_BASED => Ok(Self::CursorBased(CursorBasedData::new(&settings, params)?)),
+                _ => Err(error::Error::InvalidPaginationType(&page_settings.ty, Self::TYPES, None)),
             }
         } else {
-            Err(error::Error::InvalidPaginationType(&"None", None))
+            Err(error::Error::InvalidPaginationType(&"None", Self::TYPES, None))
         }
     }
 
@@ -1,5 +1,6 @@
 use crate::entity::SingleEntity;
 use crate::model::error;
+use crate::model::error::ErrorSource;
 use crate::Result;
 use std::cmp::Ordering;
 use std::convert::TryFrom;
@@ -52,8 +53,20 @@ impl SortQuery {
         Ok(())
     }
 
-    pub fn sort<E: SingleEntity>(&self, entities: &mut [E]) {
-        entities.sort_by(|a, b| Self::cmp_recur(a, b, &self.0))
+    pub fn sort<E: SingleEntity>(&self, entities: &mut [E]) -> Result<()> {
+        if let Some(first) = entities.first() {
+            let attributes = first.attributes();
+            for (field, _) in &self.0 {
+                if attributes.get_field(field).is_err() {
+                    return Err(error::Error::QueryFieldNotExist(
+                        field,
+                        Some(ErrorSource::from_parameter("sort")),
+                    ));
+                }
+            }
+        }
+        entities.sort_by(|a, b| Self::cmp_recur(a, b, &self.0));
+        Ok(())
     }
 
     fn cmp_recur<E: SingleEntity>(a: &E, b: &E, fields: &[(String, OrderType)]) -> Ordering {
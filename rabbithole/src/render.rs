@@ -0,0 +1,73 @@
+use crate::model::error::{Error, Errors};
+
+/// Customizes the errors before they are responded to the client, like the ids used to
+/// correlate them with the logs, and the language of the `title` and `detail`
+///
+/// The endpoints consult the `ErrorRenderer` registered in the application, or
+/// `DefaultRenderer` if there is none, with the headers of the request
+pub trait ErrorRenderer: Send + Sync {
+    /// The id of the `index`-th error responded to a request, `None` means the random one is kept
+    #[allow(unused_variables)]
+    fn error_id(&self, headers: &http::HeaderMap, index: usize, err: &Error) -> Option<String> {
+        None
+    }
+
+    /// The `title` and `detail` of `err` in one of the `languages` the client accepts, which are
+    /// ordered by preference. `None` means the English ones are kept
+    ///
+    /// The code and the parameters of the errors of Rabbithole are in `err.kind()`
+    #[allow(unused_variables)]
+    fn localize(&self, err: &Error, languages: &[String]) -> Option<(String, String)> { None }
+
+    /// Renders all the errors responded to a request
+    fn render(&self, headers: &http::HeaderMap, errors: Errors) -> Errors {
+        let languages = accept_languages(headers);
+        errors
+            .into_iter()
+            .enumerate()
+            .map(|(index, mut err)| {
+                if let Some(id) = self.error_id(headers, index, &err) {
+                    err.id = Some(id);
+                }
+                if let Some((title, detail)) = self.localize(&err, &languages) {
+                    err.title = Some(title);
+                    err.detail = Some(detail);
+                }
+                err
+            })
+            .collect::<Vec<Error>>()
+            .into()
+    }
+}
+
+/// The default `ErrorRenderer`, keeping the random ids and the English messages
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultRenderer;
+
+impl ErrorRenderer for DefaultRenderer {}
+
+/// The language tags in the `Accept-Language` header, ordered by their quality values.
+/// The wildcard and the ones with `q=0` are dropped
+pub fn accept_languages(headers: &http::HeaderMap) -> Vec<String> {
+    let mut languages: Vec<(String, f32)> = headers
+        .get_all(http::header::ACCEPT_LANGUAGE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|item| {
+            let mut parts = item.split(';').map(str::trim);
+            let tag = parts.next().filter(|tag| !tag.is_empty() && *tag != "*")?;
+            let quality = parts
+                .find_map(|param| param.strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.parse::<f32>().ok())?;
+            if quality > 0.0 {
+                Some((tag.to_string(), quality))
+            } else {
+                None
+            }
+        })
+        .collect();
+    // The sorting is stable, so the tags with the same quality keep their order
+    languages.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+    languages.into_iter().map(|(tag, _)| tag).collect()
+}
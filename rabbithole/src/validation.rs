@@ -0,0 +1,146 @@
+use crate::model::error::{Error, ErrorSource, Errors};
+use crate::model::resource::{IdentifierData, Resource};
+use serde_json::Value;
+
+/// Checks the resource in a request body before it is handed to the services, which is derived
+/// by `EntityDecorator` with the `#[entity(validate(...))]` decorators on the fields
+pub trait Validate {
+    /// Returns all of the errors found in `resource` rather than the first one, each pointing to
+    /// the invalid field.
+    /// If `partial`, the resource is the body of a `PATCH` request, so the missing fields are
+    /// not checked
+    fn validate(resource: &Resource, partial: bool) -> std::result::Result<(), Errors>;
+}
+
+/// The rules of a single field
+#[derive(Debug, Clone, Default)]
+pub struct FieldRules<'a> {
+    /// If the field is a relationship rather than an attribute
+    pub relationship: bool,
+    pub required: bool,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// The pattern which a string field should match, compiled once by the derived `Validate`
+    pub regex: Option<&'a regex::Regex>,
+    /// If the field is only set by the server, so it cannot be in the request bodies
+    pub read_only: bool,
+}
+
+/// The value of a field in `resource`, being the attribute itself, or the identifiers of a
+/// relationship
+enum FieldValue<'a> {
+    Attribute(&'a Value),
+    Relationship(&'a IdentifierData),
+}
+
+fn field_value<'a>(resource: &'a Resource, field: &str) -> Option<(FieldValue<'a>, String)> {
+    if let Ok(attr) = resource.attributes.get_field(field) {
+        Some((
+            FieldValue::Attribute(&attr.0),
+            format!("/data/attributes/{}", field),
+        ))
+    } else {
+        let data = resource
+            .relationships
+            .get(field)
+            .and_then(|relat| relat.data.as_ref());
+        data.map(|data| {
+            (
+                FieldValue::Relationship(data),
+                format!("/data/relationships/{}", field),
+            )
+        })
+    }
+}
+
+fn bounds<T: std::fmt::Display>(min: &Option<T>, max: &Option<T>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!("between {} and {}", min, max),
+        (Some(min), None) => format!("at least {}", min),
+        (None, Some(max)) => format!("at most {}", max),
+        (None, None) => "any".into(),
+    }
+}
+
+impl FieldRules<'_> {
+    /// Checks `field` of `resource`, pushing the errors found into `errors`
+    pub fn check(&self, resource: &Resource, field: &str, partial: bool, errors: &mut Vec<Error>) {
+        let (value, pointer) = match field_value(resource, field) {
+            Some(value) => value,
+            None => {
+                if self.required && !partial {
+                    let section = if self.relationship {
+                        "relationships"
+                    } else {
+                        "attributes"
+                    };
+                    errors.push(Error::FieldRequired(
+                        field,
+                        Some(ErrorSource::from_pointer(&format!(
+                            "/data/{}/{}",
+                            section, field
+                        ))),
+                    ));
+                }
+                return;
+            },
+        };
+        let source = || Some(ErrorSource::from_pointer(&pointer));
+        if self.read_only {
+            errors.push(Error::FieldNotWritable(field, source()));
+            return;
+        }
+
+        let is_empty = match value {
+            FieldValue::Attribute(value) => value.is_null(),
+            FieldValue::Relationship(IdentifierData::Single(data)) => data.is_none(),
+            FieldValue::Relationship(IdentifierData::Multiple(_)) => false,
+        };
+        if is_empty {
+            if self.required {
+                errors.push(Error::FieldRequired(field, source()));
+            }
+            return;
+        }
+
+        let length = match value {
+            FieldValue::Attribute(Value::String(s)) => Some(s.chars().count()),
+            FieldValue::Attribute(Value::Array(arr)) => Some(arr.len()),
+            FieldValue::Relationship(IdentifierData::Multiple(data)) => Some(data.len()),
+            _ => None,
+        };
+        if let Some(length) = length {
+            if self.min_length.is_some_and(|min| length < min)
+                || self.max_length.is_some_and(|max| length > max)
+            {
+                errors.push(Error::InvalidFieldLength(
+                    field,
+                    &bounds(&self.min_length, &self.max_length),
+                    length,
+                    source(),
+                ));
+            }
+        }
+
+        if let FieldValue::Attribute(Value::Number(num)) = value {
+            if let Some(num) = num.as_f64() {
+                if self.min.is_some_and(|min| num < min) || self.max.is_some_and(|max| num > max) {
+                    errors.push(Error::FieldOutOfRange(
+                        field,
+                        &bounds(&self.min, &self.max),
+                        num,
+                        source(),
+                    ));
+                }
+            }
+        }
+
+        if let (Some(regex), FieldValue::Attribute(Value::String(s))) = (self.regex, value) {
+            if !regex.is_match(s) {
+                errors.push(Error::FieldPatternNotMatch(field, regex.as_str(), source()));
+            }
+        }
+    }
+}
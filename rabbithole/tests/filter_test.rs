@@ -40,3 +40,16 @@ fn rsql_test() {
     .unwrap();
     assert_eq!(rsql_data.filter(DOGS.clone()).unwrap().len(), 2);
 }
+
+#[test]
+fn unknown_field_filter_test() {
+    let rsql_data = RsqlFilterData::new(&HashMap::from_iter(vec![(
+        "dogs".into(),
+        "color==brown".into(),
+    )]))
+    .unwrap();
+    let err = rsql_data.filter(DOGS.clone()).err().unwrap();
+    assert_eq!(err.code.as_deref(), Some("RBH-0109"));
+    assert_eq!(err.status.as_deref(), Some("400"));
+    assert_eq!(err.source.parameter.as_deref(), Some("filter[dogs]"));
+}
@@ -0,0 +1,160 @@
+extern crate rabbithole_derive as rbh_derive;
+
+use rabbithole::entity::{Entity, SingleEntity};
+use rabbithole::include::{IncludeLoader, IncludeLoaders};
+use rabbithole::model::resource::{Resource, ResourceIdentifier};
+use rabbithole::query::Query;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::iter::FromIterator;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "people")]
+#[entity(service(HumanService))]
+pub struct Human {
+    #[entity(id)]
+    pub id: String,
+    #[entity(to_many(type = "dogs"))]
+    pub dogs: Vec<String>,
+}
+
+#[derive(rbh_derive::EntityDecorator, Serialize, Deserialize, Clone)]
+#[entity(type = "dogs")]
+#[entity(service(DogService))]
+pub struct Dog {
+    #[entity(id)]
+    pub id: String,
+    pub name: String,
+    #[entity(to_one(type = "fleas"))]
+    pub flea: Option<String>,
+}
+
+/// Loads the dogs named after their ids, each of which has a flea of the same id
+struct DogLoader(Arc<AtomicUsize>);
+
+#[rabbithole::async_trait]
+impl IncludeLoader for DogLoader {
+    async fn load(
+        &self, ids: &[ResourceIdentifier], uri: &str,
+    ) -> rabbithole::Result<Vec<Resource>> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        Ok(ids
+            .iter()
+            .filter_map(|id| {
+                Dog {
+                    id: id.id.clone(),
+                    name: id.id.clone(),
+                    flea: Some(id.id.clone()),
+                }
+                .to_resource(
+                    uri,
+                    &Default::default(),
+                    &rabbithole::policy::AllowAll,
+                )
+            })
+            .collect())
+    }
+}
+
+struct FleaLoader(Arc<AtomicUsize>);
+
+#[rabbithole::async_trait]
+impl IncludeLoader for FleaLoader {
+    async fn load(
+        &self, ids: &[ResourceIdentifier], _uri: &str,
+    ) -> rabbithole::Result<Vec<Resource>> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        Ok(ids
+            .iter()
+            .map(|id| Resource {
+                id: id.clone(),
+                ..Default::default()
+            })
+            .collect())
+    }
+}
+
+fn query(include: &[&str]) -> Query {
+    Query {
+        include: Some(include.iter().map(ToString::to_string).collect()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn batched_include_test() {
+    let humans = vec![
+        Human {
+            id: "1".into(),
+            dogs: vec!["a".into(), "b".into()],
+        },
+        Human {
+            id: "2".into(),
+            dogs: vec!["b".into(), "c".into()],
+        },
+    ];
+    let (dog_calls, flea_calls) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    let loaders = IncludeLoaders::default()
+        .register("dogs", DogLoader(dog_calls.clone()))
+        .register("fleas", FleaLoader(flea_calls.clone()));
+    let uri = "https://example.com/api";
+    let mut query = query(&["dogs.flea"]);
+    query.fields = HashMap::from_iter(vec![(
+        "dogs".to_string(),
+        HashSet::from_iter(vec!["name".to_string()]),
+    )]);
+
+    let doc = humans
+        .to_document(
+            uri,
+            &query.shallow_include(),
+            "/people".parse().unwrap(),
+            Default::default(),
+            Default::default(),
+        )
+        .unwrap();
+    let doc = futures::executor::block_on(loaders.include(doc, uri, &query)).unwrap();
+    let (_, included) = doc.into_multiple().unwrap();
+
+    let mut ids: Vec<String> = included
+        .keys()
+        .map(|id| format!("{}/{}", id.ty, id.id))
+        .collect();
+    ids.sort();
+    assert_eq!(ids, vec![
+        "dogs/a", "dogs/b", "dogs/c", "fleas/a", "fleas/b", "fleas/c"
+    ]);
+    assert_eq!(dog_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(flea_calls.load(Ordering::SeqCst), 1);
+    // The sparse fieldsets are applied after the nested paths are resolved
+    let dog = &included[&ResourceIdentifier::new("dogs", "a")];
+    assert!(dog.attributes.get_field("name").is_ok());
+    assert!(dog.relationships.is_empty());
+}
+
+#[test]
+fn missing_loader_test() {
+    let humans = vec![Human {
+        id: "1".into(),
+        dogs: vec!["a".into()],
+    }];
+    let loaders = IncludeLoaders::default().register("dogs", DogLoader(Default::default()));
+    let uri = "https://example.com/api";
+    let query = query(&["dogs.flea"]);
+    let doc = humans
+        .to_document(
+            uri,
+            &query.shallow_include(),
+            "/people".parse().unwrap(),
+            Default::default(),
+            Default::default(),
+        )
+        .unwrap();
+    let err = futures::executor::block_on(loaders.include(doc, uri, &query))
+        .err()
+        .unwrap();
+    assert_eq!(err.code.as_deref(), Some("RBH-0004"));
+    assert_eq!(err.source.parameter.as_deref(), Some("include"));
+}
@@ -0,0 +1,50 @@
+pub mod common;
+
+use common::Dog;
+use rabbithole::entity::{EntityLookup, RelationshipLookup};
+use rabbithole::model::resource::{IdentifierData, ResourceIdentifier};
+
+struct Kennel(Vec<Dog>);
+
+#[rabbithole::async_trait]
+impl EntityLookup<Dog> for Kennel {
+    async fn lookup_entities(&self, ids: &[ResourceIdentifier]) -> rabbithole::Result<Vec<Dog>> {
+        Ok(self
+            .0
+            .iter()
+            .filter(|dog| ids.iter().any(|id| id.id == dog.id))
+            .cloned()
+            .collect())
+    }
+}
+
+fn identifier(id: &str) -> IdentifierData {
+    IdentifierData::Single(Some(ResourceIdentifier::new("dogs", id)))
+}
+
+#[test]
+fn to_one_lookup_test() {
+    let kennel = Kennel(vec![Dog { id: "a".into(), name: "1".into(), age: 3 }]);
+
+    let dog: Option<Dog> =
+        futures::executor::block_on(kennel.lookup_relationship("mate", &identifier("a"))).unwrap();
+    assert_eq!(dog.unwrap().id, "a");
+
+    let dog: Option<Dog> = futures::executor::block_on(
+        kennel.lookup_relationship("mate", &IdentifierData::Single(None)),
+    )
+    .unwrap();
+    assert!(dog.is_none());
+
+    // A dangling identifier is not silently dropped as `null`
+    let err = futures::executor::block_on(
+        RelationshipLookup::<Option<Dog>>::lookup_relationship(&kennel, "mate", &identifier("b")),
+    )
+    .err()
+    .unwrap();
+    assert_eq!(err.code.as_deref(), Some("RBH-0209"));
+    assert_eq!(
+        serde_json::to_value(&err.source).unwrap()["pointer"],
+        "/data/relationships/mate"
+    );
+}
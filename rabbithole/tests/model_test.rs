@@ -2,10 +2,11 @@ mod helper;
 
 use crate::helper::read_json_file;
 use rabbithole::model::document::{Document, DocumentItem, PrimaryDataItem};
-use rabbithole::model::error::Error;
+use rabbithole::model::error::{Error, Errors, RabbitholeErrorKind};
 use rabbithole::model::link::Links;
 use rabbithole::model::resource::*;
 use rabbithole::model::*;
+use rabbithole::query::page::PageQuery;
 use std::collections::HashSet;
 use std::iter::FromIterator;
 
@@ -288,6 +289,26 @@ fn it_omits_empty_error_keys() {
     );
 }
 
+#[test]
+fn errors_status_test() {
+    let with_status = |status: &str| Error {
+        status: Some(status.into()),
+        ..Default::default()
+    };
+    let errors: Errors = vec![with_status("409"), with_status("409")].into();
+    assert_eq!(errors.status(), http::StatusCode::CONFLICT);
+    let errors: Errors = vec![with_status("409"), with_status("403")].into();
+    assert_eq!(errors.status(), http::StatusCode::BAD_REQUEST);
+    let errors: Errors = vec![with_status("409"), with_status("501")].into();
+    assert_eq!(errors.status(), http::StatusCode::INTERNAL_SERVER_ERROR);
+
+    let doc: Document = errors.into();
+    assert_eq!(
+        serde_json::to_string(&doc).unwrap(),
+        r#"{"errors":[{"status":"409"},{"status":"501"}]}"#
+    );
+}
+
 #[test]
 fn it_allows_for_optional_attributes() {
     let _ = env_logger::try_init();
@@ -301,3 +322,63 @@ fn it_allows_for_optional_attributes() {
         unreachable!("err: {:?}", err);
     }
 }
+
+#[test]
+fn error_kind_test() {
+    let err = Error::IdNotMatch("1", "2", None);
+    assert_eq!(
+        err.kind(),
+        Some(&RabbitholeErrorKind::IdNotMatch {
+            expected: "1".into(),
+            found: "2".into()
+        })
+    );
+    match err.kind().cloned().unwrap() {
+        RabbitholeErrorKind::IdNotMatch { found, .. } => assert_eq!(found, "2"),
+        kind => panic!("unexpected error kind: {:?}", kind),
+    }
+
+    let kind = RabbitholeErrorKind::LackOfPaginationParams {
+        page_type: "OffsetBased".into(),
+        params: vec!["offset".into(), "limit".into()],
+    };
+    assert_eq!(kind.status(), http::StatusCode::BAD_REQUEST);
+    assert_eq!(kind.code(), "RBH-0106");
+    let boxed: Box<dyn std::error::Error> = Box::new(kind.clone());
+    assert_eq!(
+        boxed.to_string(),
+        r#"Pagination type `[OffsetBased]` need ALL of the parameters: [["offset", "limit"]]"#
+    );
+
+    let err: Error = kind.into();
+    assert_eq!(err.code.as_deref(), Some("RBH-0106"));
+    assert_eq!(err.detail, Some(boxed.to_string()));
+    let json = serde_json::to_value(&err).unwrap();
+    assert!(json.get("kind").is_none());
+    let deserialized: Error = serde_json::from_value(json).unwrap();
+    assert!(deserialized.kind().is_none());
+    assert_eq!(deserialized, err);
+}
+
+#[test]
+fn error_meta_and_headers_test() {
+    let err = Error::InvalidPaginationType("Unknown", PageQuery::TYPES, None);
+    assert_eq!(
+        serde_json::to_value(&err.meta).unwrap(),
+        serde_json::json!({ "supported": ["OffsetBased", "PageBased", "CursorBased"] })
+    );
+    assert!(err.headers().unwrap().is_empty());
+
+    let err = Error::ServiceUnavailable("under maintenance", 120, None);
+    assert_eq!(err.status.as_deref(), Some("503"));
+    assert_eq!(
+        err.headers()
+            .unwrap()
+            .get(http::header::RETRY_AFTER)
+            .unwrap(),
+        "120"
+    );
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(json["meta"], serde_json::json!({ "retryAfter": 120 }));
+    assert!(json.get("headers").is_none());
+}
@@ -9,7 +9,7 @@ use rabbithole::entity::Entity;
 use rabbithole::model::document::DocumentItem;
 use rabbithole::query::page::{Cursor, CursorBasedData, PageQuery};
 use rabbithole::query::sort::OrderType;
-use rabbithole::query::Query;
+use rabbithole::query::{PageSettings, Query, QuerySettings};
 use std::convert::TryInto;
 
 lazy_static! {
@@ -46,7 +46,7 @@ fn sort_and_page_test() {
     let uri = percent_encode(uri.as_bytes(), NON_ALPHANUMERIC);
     let uri = format!("/dogs?{}", uri.to_string());
 
-    query.sort.sort(&mut dogs);
+    query.sort.sort(&mut dogs).unwrap();
     let (dogs, _) = query.page.as_ref().unwrap().page(&dogs).unwrap();
 
     let doc = dogs
@@ -65,3 +65,48 @@ fn sort_and_page_test() {
         assert_eq!(data[1].id.id, "a");
     }
 }
+
+#[test]
+fn query_parameter_error_test() {
+    let settings = QuerySettings {
+        page: Some(PageSettings {
+            ty: "CursorBased".into(),
+        }),
+        ..Default::default()
+    };
+    let decode = |query: &str| {
+        let err = settings
+            .decode_path(&format!("/dogs?{}", query).parse().unwrap())
+            .unwrap_err();
+        (err.status.unwrap(), err.source.parameter.unwrap())
+    };
+
+    assert_eq!(decode("sort=master.name"), ("400".into(), "sort".into()));
+    assert_eq!(
+        decode("page[after]=invalid"),
+        ("400".into(), "page[after]".into())
+    );
+    assert_eq!(
+        decode("filter[dogs]=name=x"),
+        ("400".into(), "filter[dogs]".into())
+    );
+
+    let settings = QuerySettings {
+        page: Some(PageSettings {
+            ty: "Unknown".into(),
+        }),
+        ..Default::default()
+    };
+    let err = settings.decode_path(&"/dogs".parse().unwrap()).unwrap_err();
+    assert_eq!(err.status.as_deref(), Some("400"));
+    assert_eq!(err.source.parameter.as_deref(), Some("page"));
+
+    let page = PageQuery::CursorBased(CursorBasedData {
+        after: Some(Cursor { id: "c".into() }),
+        before: Some(Cursor { id: "a".into() }),
+        size: 10,
+    });
+    let err = page.page(&DOGS).err().unwrap();
+    assert_eq!(err.status.as_deref(), Some("400"));
+    assert_eq!(err.source.parameter.as_deref(), Some("page[before]"));
+}
@@ -21,13 +21,13 @@ fn one_field_sorting_test() {
     let mut dogs = DOGS.clone();
 
     let sort_query: SortQuery = vec![("name".into(), OrderType::Asc)].try_into().unwrap();
-    sort_query.sort(&mut dogs);
+    sort_query.sort(&mut dogs).unwrap();
     assert_eq!(dogs[0].id(), "a");
     assert_eq!(dogs[1].id(), "b");
     assert_eq!(dogs[2].id(), "c");
 
     let sort_query: SortQuery = vec![("age".into(), OrderType::Asc)].try_into().unwrap();
-    sort_query.sort(&mut dogs);
+    sort_query.sort(&mut dogs).unwrap();
     assert_eq!(dogs[0].id(), "c");
     assert_eq!(dogs[1].id(), "b");
     assert_eq!(dogs[2].id(), "a");
@@ -43,8 +43,19 @@ fn two_field_sorting_test() {
     ]
     .try_into()
     .unwrap();
-    sort_query.sort(&mut dogs);
+    sort_query.sort(&mut dogs).unwrap();
     assert_eq!(dogs[0].id(), "b");
     assert_eq!(dogs[1].id(), "c");
     assert_eq!(dogs[2].id(), "a");
 }
+
+#[test]
+fn unknown_field_sorting_test() {
+    let mut dogs = DOGS.clone();
+
+    let sort_query: SortQuery = vec![("color".into(), OrderType::Asc)].try_into().unwrap();
+    let err = sort_query.sort(&mut dogs).err().unwrap();
+    assert_eq!(err.code.as_deref(), Some("RBH-0109"));
+    assert_eq!(err.status.as_deref(), Some("400"));
+    assert_eq!(err.source.parameter.as_deref(), Some("sort"));
+}